@@ -9,6 +9,26 @@ pub enum SqlBuilderError {
     InvalidQuery,
     EmptyCondition,
     EmptyOnClause,
+    UpdateDeleteLimitDisabled,
+    HavingWithoutGroupBy,
+    ColumnCountMismatch {
+        expected: usize,
+        found: usize,
+    },
+    InvalidIdentifier(String),
+    InvalidArgumentCount {
+        function: String,
+        found: usize,
+    },
+    UnexpectedToken {
+        position: usize,
+        expected: String,
+        found: String,
+    },
+    Unsupported {
+        position: usize,
+        feature: String,
+    },
 }
 
 impl core::fmt::Display for SqlBuilderError {
@@ -28,6 +48,37 @@ impl core::fmt::Display for SqlBuilderError {
             Self::InvalidQuery => write!(f, "The query is invalid."),
             Self::EmptyCondition => write!(f, "The conditions cannot be empty."),
             Self::EmptyOnClause => write!(f, "The on clause cannot be empty."),
+            Self::UpdateDeleteLimitDisabled => write!(
+                f,
+                "ORDER BY and LIMIT on UPDATE and DELETE require the update-delete-limit feature."
+            ),
+            Self::HavingWithoutGroupBy => write!(
+                f,
+                "HAVING requires a GROUP BY clause or an aggregate function."
+            ),
+            Self::ColumnCountMismatch { expected, found } => write!(
+                f,
+                "Expected {} values to match the columns, found {}.",
+                expected, found
+            ),
+            Self::InvalidIdentifier(name) => write!(f, "The identifier {:?} is invalid.", name),
+            Self::InvalidArgumentCount { function, found } => write!(
+                f,
+                "The function {} does not accept {} arguments.",
+                function, found
+            ),
+            Self::UnexpectedToken {
+                position,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Expected {} at position {}, found {}.",
+                expected, position, found
+            ),
+            Self::Unsupported { position, feature } => {
+                write!(f, "{} at position {} are not supported.", feature, position)
+            }
         }
     }
 }
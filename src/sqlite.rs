@@ -1,9 +1,16 @@
 mod select;
+pub(crate) use select::TableRef;
 pub use select::*;
 
+mod compound;
+pub use compound::*;
+
 mod columns;
 pub use columns::*;
 
+mod constraints;
+pub use constraints::{ForeignKeyAction, References, TableConstraint};
+
 mod create_table;
 pub use create_table::*;
 
@@ -13,6 +20,17 @@ pub use insert::*;
 mod condition;
 pub use condition::*;
 
+mod with;
+pub(crate) use with::write_with;
+pub use with::{Cte, With};
+
+mod result_column;
+pub(crate) use result_column::write_returning;
+pub use result_column::{IntoResultColumns, ResultColumn};
+
+mod upsert;
+pub use upsert::*;
+
 mod update;
 pub use update::*;
 
@@ -22,10 +40,61 @@ pub use delete::*;
 mod join;
 pub use join::*;
 
-fn escape_value(value: &str) -> String {
+mod expr;
+pub use expr::*;
+
+mod ident;
+pub(crate) use ident::{is_keyword, write_names, write_table, TableName};
+pub use ident::{Ident, Quoting};
+
+mod functions;
+pub(crate) use functions::check_arguments;
+
+mod order;
+pub(crate) use order::write_order;
+pub use order::{Direction, Nulls, OrderTerm};
+
+mod window;
+pub use window::*;
+
+mod value;
+pub use value::*;
+
+mod tokenizer;
+
+mod parser;
+pub(crate) use parser::Parser;
+
+mod params;
+pub use params::Placeholder;
+pub(crate) use params::{SqlWriter, WriteSql};
+
+/// Encodes a string as a SQLite string literal.
+///
+/// Embedded single quotes are doubled. SQLite string literals cannot contain
+/// NUL bytes, so each NUL is spliced in with `char(0)` and the whole literal is
+/// wrapped in parentheses to keep it a single expression.
+/// # Example
+/// ```
+/// use lumus_sql_builder::sqlite::escape_value;
+///
+/// assert_eq!(escape_value("O'Brien"), "'O''Brien'");
+/// assert_eq!(escape_value("a\0b"), "('a' || char(0) || 'b')");
+/// ```
+pub fn escape_value(value: &str) -> String {
+    if value.contains('\0') {
+        let parts = value.split('\0').map(escape_value).collect::<Vec<_>>();
+        return format!("({})", parts.join(" || char(0) || "));
+    }
+
     let mut escaped_value = String::with_capacity(value.len() + 2);
     escaped_value.push('\'');
-    escaped_value.push_str(value);
+    for c in value.chars() {
+        if c == '\'' {
+            escaped_value.push('\'');
+        }
+        escaped_value.push(c);
+    }
     escaped_value.push('\'');
     escaped_value
 }
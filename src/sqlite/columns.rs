@@ -1,9 +1,13 @@
-use super::BuildableStatement;
+use super::{
+    BuildableStatement, ConflictResolution, Direction, Expr, ForeignKeyAction, Ident,
+    IntoCondition, Parser, References, SqlWriter, Value, WriteSql,
+};
 use crate::errors::SqlBuilderError;
 
 /// Represents the possible data types for a table column.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ColumnType {
+    Int,
     Integer,
     Text,
     Real,
@@ -13,12 +17,37 @@ pub enum ColumnType {
     Date,
     Time,
     Datetime,
+    Any,
+    /// A declared type such as `VARCHAR(255)`: a name with optional sizes, from which SQLite
+    /// derives the affinity of the column.
+    Custom(String),
+}
+
+impl ColumnType {
+    /// Creates a `ColumnType` from its name, recognizing the types above regardless of case.
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_ascii_uppercase().as_str() {
+            "INT" => Self::Int,
+            "INTEGER" => Self::Integer,
+            "TEXT" => Self::Text,
+            "REAL" => Self::Real,
+            "BOOLEAN" => Self::Boolean,
+            "BLOB" => Self::Blob,
+            "NUMERIC" => Self::Numeric,
+            "DATE" => Self::Date,
+            "TIME" => Self::Time,
+            "DATETIME" => Self::Datetime,
+            "ANY" => Self::Any,
+            _ => Self::Custom(name.to_string()),
+        }
+    }
 }
 
 /// Implementation of the `BuildableStatement` trait for `ColumnType`, allowing it to be printed.
 impl BuildableStatement for ColumnType {
     fn build(&self) -> String {
         String::from(match self {
+            Self::Int => "INT",
             Self::Integer => "INTEGER",
             Self::Text => "TEXT",
             Self::Real => "REAL",
@@ -28,39 +57,139 @@ impl BuildableStatement for ColumnType {
             Self::Date => "DATE",
             Self::Time => "TIME",
             Self::Datetime => "DATETIME",
+            Self::Any => "ANY",
+            Self::Custom(name) => name.as_str(),
+        })
+    }
+}
+
+/// Represents the collating sequence used to compare the values of a column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Collation {
+    Binary,
+    NoCase,
+    RTrim,
+    Custom(String),
+}
+
+impl Collation {
+    /// Creates a `Collation` from its name, recognizing SQLite's built-in sequences.
+    pub fn from_name(name: &str) -> Self {
+        match name.to_ascii_uppercase().as_str() {
+            "BINARY" => Self::Binary,
+            "NOCASE" => Self::NoCase,
+            "RTRIM" => Self::RTrim,
+            _ => Self::Custom(name.to_string()),
+        }
+    }
+}
+
+impl WriteSql for Collation {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        match self {
+            Self::Binary => w.push_str("BINARY"),
+            Self::NoCase => w.push_str("NOCASE"),
+            Self::RTrim => w.push_str("RTRIM"),
+            Self::Custom(name) if name.is_empty() => return Err(SqlBuilderError::InvalidQuery),
+            Self::Custom(name) => Ident::unqualified(name).write_sql(w)?,
+        }
+        Ok(())
+    }
+}
+
+/// Represents whether a generated column is computed on write or on read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeneratedStorage {
+    Stored,
+    Virtual,
+}
+
+/// Implementation of the `BuildableStatement` trait for `GeneratedStorage`, allowing it to be printed.
+impl BuildableStatement for GeneratedStorage {
+    fn build(&self) -> String {
+        String::from(match self {
+            Self::Stored => "STORED",
+            Self::Virtual => "VIRTUAL",
         })
     }
 }
 
 /// Represents the possible options for a table column.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ColumnOption {
-    NotNull,
-    Unique,
-    Default(String),
+    NotNull(Option<ConflictResolution>),
+    Unique(Option<ConflictResolution>),
+    Default(Value),
+    DefaultExpr(String),
     AutoIncrement,
-    PrimaryKey,
+    PrimaryKey {
+        direction: Option<Direction>,
+        on_conflict: Option<ConflictResolution>,
+    },
+    References(References),
+    Check(Expr),
+    Collate(Collation),
+    Generated(Expr, GeneratedStorage),
 }
 
-/// Implementation of the `BuildableStatement` trait for `ColumnOption`, allowing it to be printed.
-impl BuildableStatement for ColumnOption {
-    fn build(&self) -> String {
+impl WriteSql for ColumnOption {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        let conflict = |on_conflict: &Option<ConflictResolution>| match on_conflict {
+            Some(resolution) => format!(" ON CONFLICT {}", resolution.build()),
+            None => String::new(),
+        };
+
         match self {
-            Self::NotNull => "NOT NULL".to_string(),
-            Self::Unique => "UNIQUE".to_string(),
-            Self::Default(s) => format!("DEFAULT {}", s),
-            Self::AutoIncrement => "AUTOINCREMENT".to_string(),
-            Self::PrimaryKey => "PRIMARY KEY".to_string(),
+            Self::NotNull(on_conflict) => w.push_str(&format!("NOT NULL{}", conflict(on_conflict))),
+            Self::Unique(on_conflict) => w.push_str(&format!("UNIQUE{}", conflict(on_conflict))),
+            Self::Default(v) => w.push_str(&format!("DEFAULT {}", v.build())),
+            Self::DefaultExpr(e) => w.push_str(&format!("DEFAULT {}", e)),
+            Self::AutoIncrement => w.push_str("AUTOINCREMENT"),
+            Self::PrimaryKey {
+                direction,
+                on_conflict,
+            } => match direction {
+                Some(direction) => w.push_str(&format!(
+                    "PRIMARY KEY {}{}",
+                    direction.build(),
+                    conflict(on_conflict)
+                )),
+                None => w.push_str(&format!("PRIMARY KEY{}", conflict(on_conflict))),
+            },
+            Self::References(r) => r.write_sql(w)?,
+            Self::Check(condition) => {
+                if condition.is_empty() {
+                    return Err(SqlBuilderError::EmptyCondition);
+                }
+                w.push_str("CHECK (");
+                condition.write_sql(w)?;
+                w.push(')');
+            }
+            Self::Collate(collation) => {
+                w.push_str("COLLATE ");
+                collation.write_sql(w)?;
+            }
+            Self::Generated(expr, storage) => {
+                if expr.is_empty() {
+                    return Err(SqlBuilderError::EmptyValue);
+                }
+                w.push_str("GENERATED ALWAYS AS (");
+                expr.write_sql(w)?;
+                w.push_str(&format!(") {}", storage.build()));
+            }
         }
+        Ok(())
     }
 }
 
 /// Represents a table column with a name, data type, and options.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Column {
     name: String,
     column_type: Option<ColumnType>,
     options: Vec<ColumnOption>,
+    orphan_conflict: bool,
+    untyped: bool,
 }
 
 impl Column {
@@ -77,6 +206,8 @@ impl Column {
             name: name.to_string(),
             column_type: None,
             options: Vec::new(),
+            orphan_conflict: false,
+            untyped: false,
         }
     }
 
@@ -86,6 +217,13 @@ impl Column {
         self
     }
 
+    /// Specifies that the column has an `INT` data type, which unlike `INTEGER` does not make
+    /// a primary key an alias of the rowid.
+    pub fn int(mut self) -> Self {
+        self.column_type = Some(ColumnType::Int);
+        self
+    }
+
     /// Specifies that the column has a `TEXT` data type.
     pub fn text(mut self) -> Self {
         self.column_type = Some(ColumnType::Text);
@@ -134,21 +272,68 @@ impl Column {
         self
     }
 
+    /// Specifies that the column has an `ANY` data type, which `STRICT` tables store as is.
+    pub fn any(mut self) -> Self {
+        self.column_type = Some(ColumnType::Any);
+        self
+    }
+
+    /// Specifies a declared type such as `VARCHAR(255)` or `DOUBLE PRECISION`, which must be
+    /// one or more names with optional sizes.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Column;
+    ///
+    /// let col = Column::new("email").custom_type("varchar(255)").build().unwrap();
+    /// assert_eq!(col, "email VARCHAR(255)");
+    ///
+    /// let col = Column::new("ratio").custom_type("double precision").build().unwrap();
+    /// assert_eq!(col, "ratio DOUBLE PRECISION");
+    ///
+    /// assert!(Column::new("email").custom_type("TEXT; DROP TABLE users").build().is_err());
+    /// ```
+    pub fn custom_type(mut self, type_name: &str) -> Self {
+        self.column_type = Some(ColumnType::Custom(type_name.to_string()));
+        self
+    }
+
+    /// Declares the column without a data type, which SQLite gives the `BLOB` affinity.
+    /// Building a column with neither a type nor `untyped` fails.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Column;
+    ///
+    /// assert_eq!(Column::new("data").untyped().build().unwrap(), "data");
+    /// assert!(Column::new("data").build().is_err());
+    /// ```
+    pub fn untyped(mut self) -> Self {
+        self.column_type = None;
+        self.untyped = true;
+        self
+    }
+
     /// Specifies that the column cannot have `NULL` values.
     pub fn not_null(mut self) -> Self {
-        self.options.push(ColumnOption::NotNull);
+        self.options.push(ColumnOption::NotNull(None));
         self
     }
 
     /// Specifies that the column values must be unique across rows.
     pub fn unique(mut self) -> Self {
-        self.options.push(ColumnOption::Unique);
+        self.options.push(ColumnOption::Unique(None));
         self
     }
 
     /// Specifies a default value for the column.
-    pub fn default(mut self, value: &str) -> Self {
-        self.options.push(ColumnOption::Default(value.to_string()));
+    pub fn default(mut self, value: impl Into<Value>) -> Self {
+        self.options.push(ColumnOption::Default(value.into()));
+        self
+    }
+
+    /// Specifies a default expression for the column, such as `CURRENT_TIMESTAMP`.
+    pub fn default_expr(mut self, expr: &str) -> Self {
+        self.options
+            .push(ColumnOption::DefaultExpr(expr.to_string()));
         self
     }
 
@@ -160,29 +345,240 @@ impl Column {
 
     /// Specifies that the column is a primary key.
     pub fn primary_key(mut self) -> Self {
-        self.options.push(ColumnOption::PrimaryKey);
+        self.options.push(ColumnOption::PrimaryKey {
+            direction: None,
+            on_conflict: None,
+        });
+        self
+    }
+
+    /// Specifies that the column is a primary key, with its index in the given order.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Column, ConflictResolution, Direction};
+    ///
+    /// let col = Column::new("id")
+    ///     .integer()
+    ///     .primary_key_ordered(Direction::Desc)
+    ///     .on_conflict(ConflictResolution::Replace);
+    ///
+    /// assert_eq!(col.build().unwrap(), "id INTEGER PRIMARY KEY DESC ON CONFLICT REPLACE");
+    /// ```
+    pub fn primary_key_ordered(mut self, direction: Direction) -> Self {
+        self.options.push(ColumnOption::PrimaryKey {
+            direction: Some(direction),
+            on_conflict: None,
+        });
+        self
+    }
+
+    /// Specifies how a violation of the last `NOT NULL`, `UNIQUE` or `PRIMARY KEY` of the
+    /// column is resolved, and `build` fails with `InvalidQuery` without one.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Column, ConflictResolution};
+    ///
+    /// let col = Column::new("email")
+    ///     .text()
+    ///     .not_null()
+    ///     .on_conflict(ConflictResolution::Abort)
+    ///     .unique()
+    ///     .on_conflict(ConflictResolution::Ignore);
+    ///
+    /// assert_eq!(
+    ///     col.build().unwrap(),
+    ///     "email TEXT NOT NULL ON CONFLICT ABORT UNIQUE ON CONFLICT IGNORE"
+    /// );
+    /// ```
+    pub fn on_conflict(mut self, resolution: ConflictResolution) -> Self {
+        let last = self
+            .options
+            .iter_mut()
+            .rev()
+            .find_map(|option| match option {
+                ColumnOption::NotNull(on_conflict)
+                | ColumnOption::Unique(on_conflict)
+                | ColumnOption::PrimaryKey { on_conflict, .. } => Some(on_conflict),
+                _ => None,
+            });
+        match last {
+            Some(on_conflict) => *on_conflict = Some(resolution),
+            None => self.orphan_conflict = true,
+        }
+        self
+    }
+
+    /// Adds a `CHECK` constraint that every value of the column must satisfy.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Collation, Column, Expr};
+    ///
+    /// let col = Column::new("code")
+    ///     .text()
+    ///     .collate(Collation::NoCase)
+    ///     .check(Expr::length(Expr::col("code")).equal_to(3));
+    ///
+    /// assert_eq!(col.build().unwrap(), "code TEXT COLLATE NOCASE CHECK (length(code) = 3)");
+    /// ```
+    pub fn check(mut self, condition: impl IntoCondition) -> Self {
+        self.options
+            .push(ColumnOption::Check(condition.into_condition()));
+        self
+    }
+
+    /// Specifies the collating sequence used to compare the values of the column.
+    pub fn collate(mut self, collation: Collation) -> Self {
+        self.options.push(ColumnOption::Collate(collation));
+        self
+    }
+
+    /// Specifies that the column is computed from the other columns of the row.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Column, Expr, GeneratedStorage};
+    ///
+    /// let col = Column::new("total_cents")
+    ///     .integer()
+    ///     .generated(Expr::col("price_cents") * Expr::col("quantity"), GeneratedStorage::Stored);
+    ///
+    /// assert_eq!(
+    ///     col.build().unwrap(),
+    ///     "total_cents INTEGER GENERATED ALWAYS AS (price_cents * quantity) STORED"
+    /// );
+    /// ```
+    pub fn generated(mut self, expr: impl Into<Expr>, storage: GeneratedStorage) -> Self {
+        self.options
+            .push(ColumnOption::Generated(expr.into(), storage));
+        self
+    }
+
+    /// Returns the data type of the column.
+    pub(crate) fn column_type(&self) -> Option<&ColumnType> {
+        self.column_type.as_ref()
+    }
+
+    /// Returns `true` if the column is declared as the primary key.
+    pub(crate) fn is_primary_key(&self) -> bool {
+        self.options
+            .iter()
+            .any(|option| matches!(option, ColumnOption::PrimaryKey { .. }))
+    }
+
+    /// Specifies that the column references a column of another table.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Column, ForeignKeyAction};
+    ///
+    /// let col = Column::new("user_id")
+    ///     .integer()
+    ///     .not_null()
+    ///     .references("users", "id")
+    ///     .on_delete(ForeignKeyAction::Cascade)
+    ///     .deferred();
+    ///
+    /// assert_eq!(
+    ///     col.build().unwrap(),
+    ///     "user_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED"
+    /// );
+    /// ```
+    pub fn references(self, table: &str, column: &str) -> Self {
+        self.option(ColumnOption::References(References::new(table, [column])))
+    }
+
+    /// Specifies the action taken when the referenced row is deleted. Applies to the last
+    /// `references` of the column, and `build` fails with `EmptyTableName` without one.
+    pub fn on_delete(self, action: ForeignKeyAction) -> Self {
+        self.map_references(|r| r.on_delete(action))
+    }
+
+    /// Specifies the action taken when the referenced key is updated. Applies to the last
+    /// `references` of the column, and `build` fails with `EmptyTableName` without one.
+    pub fn on_update(self, action: ForeignKeyAction) -> Self {
+        self.map_references(|r| r.on_update(action))
+    }
+
+    /// Defers the check of the reference until the transaction commits. Applies to the last
+    /// `references` of the column, and `build` fails with `EmptyTableName` without one.
+    pub fn deferred(self) -> Self {
+        self.map_references(References::deferred)
+    }
+
+    /// Adds an option to the column.
+    pub(crate) fn option(mut self, option: ColumnOption) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    fn map_references(mut self, f: impl FnOnce(References) -> References) -> Self {
+        let last = self
+            .options
+            .iter_mut()
+            .rev()
+            .find_map(|option| match option {
+                ColumnOption::References(r) => Some(r),
+                _ => None,
+            });
+        match last {
+            Some(r) => *r = f(r.clone()),
+            None => self
+                .options
+                .push(ColumnOption::References(f(References::new(
+                    "",
+                    Vec::<String>::new(),
+                )))),
+        }
         self
     }
 
     /// Builds and returns the SQL representation of the column.
     pub fn build(&self) -> Result<String, SqlBuilderError> {
+        self.render()
+    }
+}
+
+impl WriteSql for Column {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
         if self.name.is_empty() {
             return Err(SqlBuilderError::EmptyColumnName);
         }
 
         let column_type = match &self.column_type {
-            Some(ct) => ct.build(),
+            Some(ColumnType::Custom(name)) => Parser::parse(name, Parser::parse_column_type)
+                .map(|name| format!(" {}", name))
+                .map_err(|_| SqlBuilderError::InvalidColumnType)?,
+            Some(ct) => format!(" {}", ct.build()),
+            None if self.untyped => String::new(),
             None => return Err(SqlBuilderError::InvalidColumnType),
         };
 
-        let options_str = self
+        if self.orphan_conflict {
+            return Err(SqlBuilderError::InvalidQuery);
+        }
+
+        let generated = self
             .options
             .iter()
-            .map(|opt| opt.build())
-            .collect::<Vec<String>>()
-            .join(" ");
+            .any(|option| matches!(option, ColumnOption::Generated(..)));
+        let conflicting = self.options.iter().any(|option| {
+            matches!(
+                option,
+                ColumnOption::Default(_)
+                    | ColumnOption::DefaultExpr(_)
+                    | ColumnOption::PrimaryKey { .. }
+            )
+        });
+        if generated && conflicting {
+            return Err(SqlBuilderError::InvalidQuery);
+        }
+
+        Ident::unqualified(&self.name).write_sql(w)?;
+        w.push_str(&column_type);
+        for option in &self.options {
+            w.push(' ');
+            option.write_sql(w)?;
+        }
 
-        Ok(format!("{} {} {}", self.name, column_type, options_str))
+        Ok(())
     }
 }
 
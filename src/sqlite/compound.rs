@@ -0,0 +1,239 @@
+use super::{
+    write_order, BuildableStatement, Expr, OrderTerm, Parser, Placeholder, Quoting, Select,
+    SqlWriter, Value, WriteSql,
+};
+use crate::errors::SqlBuilderError;
+
+/// Represents the set operators that join the branches of a compound select.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompoundOperator {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+/// Implementation of the `BuildableStatement` trait for `CompoundOperator`, allowing it to be printed.
+impl BuildableStatement for CompoundOperator {
+    fn build(&self) -> String {
+        String::from(match self {
+            Self::Union => "UNION",
+            Self::UnionAll => "UNION ALL",
+            Self::Intersect => "INTERSECT",
+            Self::Except => "EXCEPT",
+        })
+    }
+}
+
+/// Represents a compound SELECT, chaining `Select` builders with set operators.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compound {
+    first: Select,
+    rest: Vec<(CompoundOperator, Select)>,
+    order: Vec<OrderTerm>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+    quoting: Option<Quoting>,
+}
+
+impl Compound {
+    /// Creates a new `Compound` whose first branch is the given select.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Compound, Select};
+    ///
+    /// let mut users = Select::new("users");
+    /// users.columns("name");
+    /// let mut admins = Select::new("admins");
+    /// admins.columns("name");
+    ///
+    /// let mut compound = Compound::new(users);
+    /// compound.union(admins).order("name").limit(10);
+    ///
+    /// assert_eq!(
+    ///     compound.build().unwrap(),
+    ///     "SELECT name FROM users UNION SELECT name FROM admins ORDER BY name LIMIT 10;"
+    /// );
+    /// ```
+    pub fn new(select: Select) -> Self {
+        Self {
+            first: select,
+            rest: Vec::new(),
+            order: Vec::new(),
+            limit: None,
+            offset: None,
+            quoting: None,
+        }
+    }
+
+    /// Creates a new `Compound` instance from a SQL query string.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Compound;
+    ///
+    /// let query = "SELECT id FROM a EXCEPT SELECT id FROM b ORDER BY id;";
+    /// let compound = Compound::from(query).unwrap();
+    /// assert_eq!(compound.build().unwrap(), query);
+    /// ```
+    pub fn from(query: &str) -> Result<Compound, SqlBuilderError> {
+        Parser::parse(query, Parser::parse_compound)
+    }
+
+    /// Adds a branch joined with `UNION`.
+    pub fn union(&mut self, select: Select) -> &mut Self {
+        self.branch(CompoundOperator::Union, select)
+    }
+
+    /// Adds a branch joined with `UNION ALL`.
+    pub fn union_all(&mut self, select: Select) -> &mut Self {
+        self.branch(CompoundOperator::UnionAll, select)
+    }
+
+    /// Adds a branch joined with `INTERSECT`.
+    pub fn intersect(&mut self, select: Select) -> &mut Self {
+        self.branch(CompoundOperator::Intersect, select)
+    }
+
+    /// Adds a branch joined with `EXCEPT`.
+    pub fn except(&mut self, select: Select) -> &mut Self {
+        self.branch(CompoundOperator::Except, select)
+    }
+
+    /// Adds a branch joined with the given operator.
+    pub fn branch(&mut self, operator: CompoundOperator, select: Select) -> &mut Self {
+        self.rest.push((operator, select));
+        self
+    }
+
+    /// Specifies the ordering of the whole result.
+    pub fn order(&mut self, order: &str) -> &mut Self {
+        self.order = vec![OrderTerm::new(Expr::raw(order))];
+        self
+    }
+
+    /// Adds terms to the ordering of the whole result.
+    pub fn order_by<I, T>(&mut self, order: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OrderTerm>,
+    {
+        self.order.extend(order.into_iter().map(Into::into));
+        self
+    }
+
+    /// Specifies the maximum number of rows of the whole result.
+    pub fn limit(&mut self, limit: u32) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Specifies the offset of the whole result.
+    pub fn offset(&mut self, offset: u32) -> &mut Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Sets when the names of the statement are written between quotes, for names that do
+    /// not set their own policy.
+    pub fn quoting(&mut self, quoting: Quoting) -> &mut Self {
+        self.quoting = Some(quoting);
+        self
+    }
+
+    /// Builds and returns the SQL statement for the compound query.
+    pub fn build(&self) -> Result<String, SqlBuilderError> {
+        Ok(format!("{};", self.render()?))
+    }
+
+    /// Builds the SQL statement with placeholders and returns the values to bind.
+    pub fn build_with_params(
+        &self,
+        placeholder: Placeholder,
+    ) -> Result<(String, Vec<Value>), SqlBuilderError> {
+        let (sql, params) = self.render_with_params(placeholder)?;
+        Ok((format!("{};", sql), params))
+    }
+
+    /// Returns the last branch of the compound.
+    pub(crate) fn last_branch(&self) -> &Select {
+        self.rest.last().map_or(&self.first, |(_, select)| select)
+    }
+
+    /// Moves the `ORDER BY`, `LIMIT` and `OFFSET` parsed with the last branch onto the compound.
+    pub(crate) fn take_trailing_clauses(&mut self) {
+        if let Some((_, last)) = self.rest.last_mut() {
+            (self.order, self.limit, self.offset) = last.take_order_and_limit();
+        }
+    }
+
+    /// Returns `true` if the SQL ends with a `WHERE`, `ORDER BY` or `LIMIT` clause, so an
+    /// upsert's `ON` that follows cannot be read as a join constraint.
+    pub(crate) fn ends_unambiguously(&self) -> bool {
+        let last = self.last_branch();
+        last.has_condition()
+            || last.has_order_or_limit()
+            || !self.order.is_empty()
+            || self.limit.is_some()
+            || self.offset.is_some()
+    }
+}
+
+impl From<Select> for Compound {
+    fn from(select: Select) -> Self {
+        Self::new(select)
+    }
+}
+
+impl Compound {
+    /// Internal method to write the statement under the quoting policy set on the writer.
+    fn write_statement(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        if !self.rest.is_empty() && self.first.has_order_or_limit() {
+            return Err(SqlBuilderError::InvalidQuery);
+        }
+
+        let mut expected = self.first.column_count();
+        for (_, select) in &self.rest {
+            if select.has_with() || select.has_order_or_limit() {
+                return Err(SqlBuilderError::InvalidQuery);
+            }
+            match (expected, select.column_count()) {
+                (Some(expected), Some(found)) if expected != found => {
+                    return Err(SqlBuilderError::ColumnCountMismatch { expected, found });
+                }
+                (None, found) => expected = found,
+                _ => {}
+            }
+        }
+
+        self.first.write_sql(w)?;
+        for (operator, select) in &self.rest {
+            w.push_str(&format!(" {} ", operator.build()));
+            select.write_sql(w)?;
+        }
+
+        write_order(w, &self.order)?;
+
+        if let Some(limit) = &self.limit {
+            w.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        if let Some(offset) = &self.offset {
+            w.push_str(&format!(" OFFSET {}", offset));
+        }
+
+        Ok(())
+    }
+}
+
+impl WriteSql for Compound {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        w.with_quoting(self.quoting, |w| self.write_statement(w))
+    }
+}
+
+/// Implementation of the `BuildableStatement` trait for `Compound`, allowing it to be printed.
+impl BuildableStatement for Compound {
+    fn build(&self) -> String {
+        self.build().unwrap()
+    }
+}
@@ -1,10 +1,59 @@
-use super::{escape_value, BuildableStatement};
+use super::{Compound, Expr, Ident, Placeholder, SqlWriter, Value, WriteSql};
 use crate::errors::SqlBuilderError;
 
+/// Represents the connector pending between two predicates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Connector {
+    And,
+    Or,
+}
+
+/// Represents one level of nesting, held as `OR`-separated runs of `AND`-ed predicates.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Group {
+    terms: Vec<Vec<Expr>>,
+    connector: Option<Connector>,
+}
+
+impl Group {
+    fn push(&mut self, expr: Expr) {
+        match (self.terms.last_mut(), self.connector.take()) {
+            (Some(run), Some(Connector::And) | None) => run.push(expr),
+            _ => self.terms.push(vec![expr]),
+        }
+    }
+
+    fn to_expr(&self) -> Option<Expr> {
+        self.terms
+            .iter()
+            .filter_map(|run| run.iter().cloned().reduce(Expr::and))
+            .reduce(Expr::or)
+    }
+}
+
 /// Represents a WHERE clause builder for SQL queries.
-#[derive(Debug)]
+///
+/// Predicates are collected into an [`Expr`] tree, so `AND` binds tighter than `OR`,
+/// repeated connectors collapse into one, a trailing connector is ignored and any
+/// group left open by `nest` is closed when the clause is built.
+///
+/// Fields are column names, which may be qualified or quoted; anything else is reported
+/// as an invalid identifier by `build`. Predicates over other SQL are added with `expr`.
+/// # Example
+/// ```
+/// use lumus_sql_builder::sqlite::{Expr, Where};
+///
+/// let mut condition = Where::new();
+/// condition.equal_to("lower(name)", "x");
+/// assert!(condition.build().is_err());
+///
+/// let mut condition = Where::new();
+/// condition.expr(Expr::lower(Expr::col("name")).equal_to("x"));
+/// assert_eq!(condition.build().unwrap(), "lower(name) = 'x'");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 pub struct Where {
-    statement: String,
+    groups: Vec<Group>,
 }
 
 impl Where {
@@ -16,11 +65,11 @@ impl Where {
     /// let mut condition = Where::new();
     /// condition.equal_to("name", "Dayvson Spacca");
     ///
-    /// assert_eq!(condition.build(), "name = 'Dayvson Spacca'")
+    /// assert_eq!(condition.build().unwrap(), "name = 'Dayvson Spacca'")
     /// ```
     pub fn new() -> Self {
         Self {
-            statement: String::new(),
+            groups: vec![Group::default()],
         }
     }
 
@@ -30,178 +79,269 @@ impl Where {
     /// use lumus_sql_builder::sqlite::Where;
     ///
     /// let mut condition = Where::from("name = 'Dayvson Spacca'");
-    /// condition.and().greater_than("age", "21");
+    /// condition.and().greater_than("age", 21);
     ///
-    /// assert_eq!(condition.build(), "name = 'Dayvson Spacca' AND age > '21'");
+    /// assert_eq!(condition.build().unwrap(), "(name = 'Dayvson Spacca') AND age > 21");
     /// ```
     pub fn from(statement: &str) -> Self {
-        Self {
-            statement: statement.to_string(),
+        let mut condition = Self::new();
+        if !statement.trim().is_empty() {
+            condition.push(Expr::raw(statement.trim()));
         }
+        condition
+    }
+
+    /// Adds an arbitrary expression as a predicate.
+    pub fn expr(&mut self, expr: impl IntoCondition) -> &mut Self {
+        self.push(expr.into_condition());
+        self
     }
 
     /// Adds an equality condition (`field = value`) to the WHERE clause.
-    pub fn equal_to(&mut self, field: &str, value: &str) -> &mut Self {
-        self.add_predicate(field, "=", escape_value(value).as_str())
-            .unwrap();
+    pub fn equal_to(&mut self, field: &str, value: impl Into<Value>) -> &mut Self {
+        self.add_predicate(field, |col| col.equal_to(Expr::value(value)));
         self
     }
 
     /// Adds a not equal condition (`field != value`) to the WHERE clause.
-    pub fn not_equal_to(&mut self, field: &str, value: &str) -> &mut Self {
-        self.add_predicate(field, "!=", escape_value(value).as_str())
-            .unwrap();
+    pub fn not_equal_to(&mut self, field: &str, value: impl Into<Value>) -> &mut Self {
+        self.add_predicate(field, |col| col.not_equal_to(Expr::value(value)));
         self
     }
 
     /// Adds a greater than condition (`field > value`) to the WHERE clause.
-    pub fn greater_than(&mut self, field: &str, value: &str) -> &mut Self {
-        self.add_predicate(field, ">", escape_value(value).as_str())
-            .unwrap();
+    pub fn greater_than(&mut self, field: &str, value: impl Into<Value>) -> &mut Self {
+        self.add_predicate(field, |col| col.greater_than(Expr::value(value)));
         self
     }
 
     /// Adds a greater than or equal condition (`field >= value`) to the WHERE clause.
-    pub fn greater_than_equal(&mut self, field: &str, value: &str) -> &mut Self {
-        self.add_predicate(field, ">=", escape_value(value).as_str())
-            .unwrap();
+    pub fn greater_than_equal(&mut self, field: &str, value: impl Into<Value>) -> &mut Self {
+        self.add_predicate(field, |col| col.greater_than_equal(Expr::value(value)));
         self
     }
 
     /// Adds a less than condition (`field < value`) to the WHERE clause.
-    pub fn less_than(&mut self, field: &str, value: &str) -> &mut Self {
-        self.add_predicate(field, "<", escape_value(value).as_str())
-            .unwrap();
+    pub fn less_than(&mut self, field: &str, value: impl Into<Value>) -> &mut Self {
+        self.add_predicate(field, |col| col.less_than(Expr::value(value)));
         self
     }
 
     /// Adds a less than or equal condition (`field <= value`) to the WHERE clause.
-    pub fn less_than_equal(&mut self, field: &str, value: &str) -> &mut Self {
-        self.add_predicate(field, "<=", escape_value(value).as_str())
-            .unwrap();
+    pub fn less_than_equal(&mut self, field: &str, value: impl Into<Value>) -> &mut Self {
+        self.add_predicate(field, |col| col.less_than_equal(Expr::value(value)));
         self
     }
 
     /// Adds a `IS NULL` condition (`field IS NULL`) to the WHERE clause.
     pub fn is_null(&mut self, field: &str) -> &mut Self {
-        self.add_predicate(field, "IS NULL", "").unwrap();
+        self.add_predicate(field, Expr::is_null);
         self
     }
 
     /// Adds a `IS NOT NULL` condition (`field IS NOT NULL`) to the WHERE clause.
     pub fn is_not_null(&mut self, field: &str) -> &mut Self {
-        self.add_predicate(field, "IS NOT NULL", "").unwrap();
+        self.add_predicate(field, Expr::is_not_null);
         self
     }
 
     /// Adds an `IN` condition (`field IN (values)`) to the WHERE clause.
-    pub fn inside(&mut self, field: &str, values: Vec<&str>) -> &mut Self {
-        self.add_predicate(
-            field,
-            "IN",
-            &format!(
-                "({})",
-                values
-                    .iter()
-                    .map(|v| escape_value(v))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            ),
-        )
-        .unwrap();
+    pub fn inside<T: Into<Value>>(&mut self, field: &str, values: Vec<T>) -> &mut Self {
+        self.add_predicate(field, |col| {
+            col.inside(values.into_iter().map(Expr::value).collect::<Vec<_>>())
+        });
         self
     }
 
     /// Adds a `NOT IN` condition (`field NOT IN (values)`) to the WHERE clause.
-    pub fn not_inside(&mut self, field: &str, values: Vec<&str>) -> &mut Self {
-        self.add_predicate(
-            field,
-            "NOT IN",
-            &format!(
-                "({})",
-                values
-                    .iter()
-                    .map(|v| escape_value(v))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            ),
-        )
-        .unwrap();
+    pub fn not_inside<T: Into<Value>>(&mut self, field: &str, values: Vec<T>) -> &mut Self {
+        self.add_predicate(field, |col| {
+            col.not_inside(values.into_iter().map(Expr::value).collect::<Vec<_>>())
+        });
+        self
+    }
+
+    /// Adds an `IN` condition against a subquery (`field IN (SELECT ...)`) to the WHERE clause.
+    pub fn in_subquery(&mut self, field: &str, query: impl Into<Compound>) -> &mut Self {
+        self.add_predicate(field, |col| col.in_subquery(query));
+        self
+    }
+
+    /// Adds a `NOT IN` condition against a subquery (`field NOT IN (SELECT ...)`) to the WHERE clause.
+    pub fn not_in_subquery(&mut self, field: &str, query: impl Into<Compound>) -> &mut Self {
+        self.add_predicate(field, |col| col.not_in_subquery(query));
+        self
+    }
+
+    /// Adds an `EXISTS (SELECT ...)` condition to the WHERE clause.
+    pub fn exists(&mut self, query: impl Into<Compound>) -> &mut Self {
+        self.push(Expr::exists(query));
+        self
+    }
+
+    /// Adds a `NOT EXISTS (SELECT ...)` condition to the WHERE clause.
+    pub fn not_exists(&mut self, query: impl Into<Compound>) -> &mut Self {
+        self.push(Expr::not_exists(query));
         self
     }
 
     /// Adds a `LIKE` condition (`field LIKE value`) to the WHERE clause.
-    pub fn like(&mut self, field: &str, value: &str) -> &mut Self {
-        self.add_predicate(field, "LIKE", escape_value(value).as_str())
-            .unwrap();
+    pub fn like(&mut self, field: &str, value: impl Into<Value>) -> &mut Self {
+        self.add_predicate(field, |col| col.like(Expr::value(value)));
         self
     }
 
     /// Adds a `NOT LIKE` condition (`field NOT LIKE value`) to the WHERE clause.
-    pub fn not_like(&mut self, field: &str, value: &str) -> &mut Self {
-        self.add_predicate(field, "NOT LIKE", escape_value(value).as_str())
-            .unwrap();
+    pub fn not_like(&mut self, field: &str, value: impl Into<Value>) -> &mut Self {
+        self.add_predicate(field, |col| col.not_like(Expr::value(value)));
         self
     }
 
-    /// Appends `AND` to the current statement in the WHERE clause.
+    /// Joins the previous and the next predicate with `AND`.
     pub fn and(&mut self) -> &mut Self {
-        self.statement.push_str(" AND ");
+        self.current().connector = Some(Connector::And);
         self
     }
 
-    /// Appends `OR` to the current statement in the WHERE clause.
+    /// Joins the previous and the next predicate with `OR`.
     pub fn or(&mut self) -> &mut Self {
-        self.statement.push_str(" OR ");
+        self.current().connector = Some(Connector::Or);
         self
     }
 
-    /// Appends a left parenthesis `(` to the current statement in the WHERE clause.
+    /// Opens a parenthesized group in the WHERE clause.
     pub fn nest(&mut self) -> &mut Self {
-        self.statement.push('(');
+        self.groups.push(Group::default());
         self
     }
 
-    /// Appends a right parenthesis `)` to the current statement in the WHERE clause.
+    /// Closes the innermost group opened by `nest`.
     pub fn unnest(&mut self) -> &mut Self {
-        self.statement.push(')');
+        if self.groups.len() > 1 {
+            let group = self.groups.pop().unwrap();
+            if let Some(expr) = group.to_expr() {
+                self.push(expr.nested());
+            }
+        }
         self
     }
 
-    /// Constructs and returns the final SQL statement represented by the WHERE clause.
-    pub fn build(&self) -> String {
-        self.statement.trim().to_string()
-    }
-
-    /// Internal method to add a predicate (`field predicate value`) to the WHERE clause.
-    fn add_predicate(
-        &mut self,
-        field: &str,
-        predicate: &str,
-        value: &str,
-    ) -> Result<&mut Self, SqlBuilderError> {
-        if field.is_empty() {
-            return Err(SqlBuilderError::EmptyColumnName);
+    /// Returns the expression tree of the WHERE clause, closing any open group.
+    pub fn to_expr(&self) -> Option<Expr> {
+        let mut groups = self.groups.clone();
+        while groups.len() > 1 {
+            let group = groups.pop().unwrap();
+            if let Some(expr) = group.to_expr() {
+                groups.last_mut().unwrap().push(expr.nested());
+            }
         }
+        groups[0].to_expr()
+    }
 
-        if predicate == "IS NULL" || predicate == "IS NOT NULL" {
-            self.statement.push_str(&format!("{} {}", field, predicate));
-            return Ok(self);
-        }
+    /// Constructs and returns the final SQL statement represented by the WHERE clause.
+    pub fn build(&self) -> Result<String, SqlBuilderError> {
+        self.render()
+    }
 
-        if value.is_empty() {
-            return Err(SqlBuilderError::EmptyValue);
-        }
+    /// Constructs the WHERE clause with placeholders instead of literal values.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Placeholder, Value, Where};
+    ///
+    /// let mut condition = Where::new();
+    /// condition.equal_to("name", "Dayvson Spacca");
+    ///
+    /// let (sql, params) = condition.build_with_params(Placeholder::Anonymous).unwrap();
+    /// assert_eq!(sql, "name = ?");
+    /// assert_eq!(params, vec![Value::from("Dayvson Spacca")]);
+    /// ```
+    pub fn build_with_params(
+        &self,
+        placeholder: Placeholder,
+    ) -> Result<(String, Vec<Value>), SqlBuilderError> {
+        self.render_with_params(placeholder)
+    }
+
+    /// Returns `true` if the WHERE clause has no content.
+    pub fn is_empty(&self) -> bool {
+        self.to_expr().is_none()
+    }
+
+    /// Internal method to return the innermost open group.
+    fn current(&mut self) -> &mut Group {
+        self.groups.last_mut().unwrap()
+    }
+
+    /// Internal method to add a predicate to the innermost open group.
+    fn push(&mut self, expr: Expr) {
+        self.current().push(expr);
+    }
+
+    /// Internal method to add a predicate built from a column reference to the WHERE clause.
+    /// A field that is not a name is reported by `build`, like an empty field.
+    fn add_predicate(&mut self, field: &str, predicate: impl FnOnce(Expr) -> Expr) {
+        self.push(predicate(Expr::from(Ident::strict(field))));
+    }
+}
 
-        self.statement
-            .push_str(&format!("{} {} {}", field, predicate, value));
-        Ok(self)
+impl Default for Where {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-/// Implementation of the `BuildableStatement` trait for `ColumnOption`, allowing it to be printed.
-impl BuildableStatement for Where {
-    fn build(&self) -> String {
-        self.build()
+/// Implemented by the types accepted where a condition is expected, as in `WHERE`, `ON` or
+/// `CHECK`. Strings are left out, so that SQL text is never taken for a quoted value there;
+/// it is passed as `Where::from` or `Expr::raw` instead.
+/// # Example
+/// ```
+/// use lumus_sql_builder::sqlite::{Delete, Where};
+///
+/// let mut delete = Delete::new("users");
+/// delete.condition(Where::from("age > 18"));
+/// assert_eq!(delete.build().unwrap(), "DELETE FROM users WHERE age > 18;");
+/// ```
+///
+/// ```compile_fail
+/// use lumus_sql_builder::sqlite::Delete;
+///
+/// Delete::new("users").condition("age > 18");
+/// ```
+pub trait IntoCondition {
+    fn into_condition(self) -> Expr;
+}
+
+impl IntoCondition for Expr {
+    fn into_condition(self) -> Expr {
+        self
+    }
+}
+
+impl IntoCondition for Where {
+    fn into_condition(self) -> Expr {
+        self.into()
+    }
+}
+
+/// Allows a raw SQL string to be used wherever a `Where` is expected.
+impl From<String> for Where {
+    fn from(statement: String) -> Self {
+        Self::from(statement.as_str())
+    }
+}
+
+/// Converts the WHERE clause into its expression tree; an empty clause becomes empty raw SQL.
+impl From<Where> for Expr {
+    fn from(condition: Where) -> Self {
+        condition.to_expr().unwrap_or_else(|| Expr::raw(""))
+    }
+}
+
+impl WriteSql for Where {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        match self.to_expr() {
+            Some(expr) => expr.write_sql(w),
+            None => Ok(()),
+        }
     }
 }
@@ -0,0 +1,293 @@
+use super::{BuildableStatement, Expr, Ident, IntoCondition, SqlWriter, WriteSql};
+use crate::errors::SqlBuilderError;
+
+/// Writes a parenthesized, comma-separated list of column names.
+fn write_column_list(w: &mut SqlWriter, columns: &[String]) -> Result<(), SqlBuilderError> {
+    w.push('(');
+    for (i, col) in columns.iter().enumerate() {
+        if col.is_empty() {
+            return Err(SqlBuilderError::EmptyColumnName);
+        }
+        if i > 0 {
+            w.push_str(", ");
+        }
+        Ident::unqualified(col).write_sql(w)?;
+    }
+    w.push(')');
+    Ok(())
+}
+
+/// Represents the action taken on the child rows when a referenced row is deleted or updated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForeignKeyAction {
+    Cascade,
+    SetNull,
+    SetDefault,
+    Restrict,
+    NoAction,
+}
+
+/// Implementation of the `BuildableStatement` trait for `ForeignKeyAction`, allowing it to be printed.
+impl BuildableStatement for ForeignKeyAction {
+    fn build(&self) -> String {
+        String::from(match self {
+            Self::Cascade => "CASCADE",
+            Self::SetNull => "SET NULL",
+            Self::SetDefault => "SET DEFAULT",
+            Self::Restrict => "RESTRICT",
+            Self::NoAction => "NO ACTION",
+        })
+    }
+}
+
+/// Represents the `REFERENCES` clause of a foreign key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct References {
+    table: String,
+    columns: Vec<String>,
+    on_delete: Option<ForeignKeyAction>,
+    on_update: Option<ForeignKeyAction>,
+    deferred: bool,
+}
+
+impl References {
+    /// Creates a new `References` to the given table and columns. Without columns, the
+    /// foreign key refers to the primary key of the table.
+    pub fn new<I, S>(table: &str, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            table: table.to_string(),
+            columns: to_strings(columns),
+            on_delete: None,
+            on_update: None,
+            deferred: false,
+        }
+    }
+
+    /// Specifies the action taken when the referenced row is deleted.
+    pub fn on_delete(mut self, action: ForeignKeyAction) -> Self {
+        self.on_delete = Some(action);
+        self
+    }
+
+    /// Specifies the action taken when the referenced key is updated.
+    pub fn on_update(mut self, action: ForeignKeyAction) -> Self {
+        self.on_update = Some(action);
+        self
+    }
+
+    /// Defers the check of the foreign key until the transaction commits, with
+    /// `DEFERRABLE INITIALLY DEFERRED`.
+    pub fn deferred(mut self) -> Self {
+        self.deferred = true;
+        self
+    }
+
+    /// Returns the number of referenced columns.
+    pub(crate) fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Builds and returns the SQL representation of the clause.
+    pub fn build(&self) -> Result<String, SqlBuilderError> {
+        self.render()
+    }
+}
+
+impl WriteSql for References {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        if self.table.is_empty() {
+            return Err(SqlBuilderError::EmptyTableName);
+        }
+
+        w.push_str("REFERENCES ");
+        Ident::unqualified(&self.table).write_sql(w)?;
+        if !self.columns.is_empty() {
+            w.push(' ');
+            write_column_list(w, &self.columns)?;
+        }
+
+        if let Some(action) = &self.on_delete {
+            w.push_str(&format!(" ON DELETE {}", action.build()));
+        }
+
+        if let Some(action) = &self.on_update {
+            w.push_str(&format!(" ON UPDATE {}", action.build()));
+        }
+
+        if self.deferred {
+            w.push_str(" DEFERRABLE INITIALLY DEFERRED");
+        }
+        Ok(())
+    }
+}
+
+/// Represents the kinds of table constraints.
+#[derive(Debug, Clone, PartialEq)]
+enum ConstraintKind {
+    PrimaryKey(Vec<String>),
+    Unique(Vec<String>),
+    Check(Expr),
+    ForeignKey(Vec<String>, References),
+}
+
+/// Represents a constraint declared after the columns of a `CREATE TABLE`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableConstraint {
+    name: Option<String>,
+    kind: ConstraintKind,
+}
+
+impl TableConstraint {
+    /// Creates a `PRIMARY KEY` over the given columns.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Column, CreateTable, TableConstraint};
+    ///
+    /// let table = CreateTable::new("memberships", vec![
+    ///     Column::new("user_id").integer(),
+    ///     Column::new("team_id").integer(),
+    /// ])
+    /// .constraint(TableConstraint::primary_key(["user_id", "team_id"]).name("pk_memberships"));
+    ///
+    /// assert_eq!(
+    ///     table.build().unwrap(),
+    ///     "CREATE TABLE memberships (user_id INTEGER, team_id INTEGER, CONSTRAINT pk_memberships PRIMARY KEY (user_id, team_id));"
+    /// );
+    /// ```
+    pub fn primary_key<I, S>(columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::of(ConstraintKind::PrimaryKey(to_strings(columns)))
+    }
+
+    /// Creates a `UNIQUE` constraint over the given columns.
+    pub fn unique<I, S>(columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::of(ConstraintKind::Unique(to_strings(columns)))
+    }
+
+    /// Creates a `CHECK` constraint that every row must satisfy.
+    pub fn check(condition: impl IntoCondition) -> Self {
+        Self::of(ConstraintKind::Check(condition.into_condition()))
+    }
+
+    /// Creates a `FOREIGN KEY` from the given columns to the referenced table.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{References, TableConstraint};
+    ///
+    /// let constraint = TableConstraint::foreign_key(["user_id"], References::new("users", ["id"]));
+    /// assert_eq!(
+    ///     constraint.build().unwrap(),
+    ///     "FOREIGN KEY (user_id) REFERENCES users (id)"
+    /// );
+    /// ```
+    pub fn foreign_key<I, S>(columns: I, references: References) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::of(ConstraintKind::ForeignKey(to_strings(columns), references))
+    }
+
+    fn of(kind: ConstraintKind) -> Self {
+        Self { name: None, kind }
+    }
+
+    /// Names the constraint with a `CONSTRAINT name` prefix.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Returns `true` if the constraint is a `PRIMARY KEY`.
+    pub(crate) fn is_primary_key(&self) -> bool {
+        matches!(self.kind, ConstraintKind::PrimaryKey(_))
+    }
+
+    /// Returns the columns of the table the constraint applies to.
+    pub(crate) fn columns(&self) -> &[String] {
+        match &self.kind {
+            ConstraintKind::PrimaryKey(columns)
+            | ConstraintKind::Unique(columns)
+            | ConstraintKind::ForeignKey(columns, _) => columns,
+            ConstraintKind::Check(_) => &[],
+        }
+    }
+
+    /// Builds and returns the SQL representation of the constraint.
+    pub fn build(&self) -> Result<String, SqlBuilderError> {
+        self.render()
+    }
+}
+
+impl WriteSql for TableConstraint {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        if let Some(name) = &self.name {
+            if name.is_empty() {
+                return Err(SqlBuilderError::InvalidQuery);
+            }
+            w.push_str("CONSTRAINT ");
+            Ident::unqualified(name).write_sql(w)?;
+            w.push(' ');
+        }
+
+        if !matches!(self.kind, ConstraintKind::Check(_)) && self.columns().is_empty() {
+            return Err(SqlBuilderError::NoColumnsSpecified);
+        }
+
+        match &self.kind {
+            ConstraintKind::PrimaryKey(columns) => {
+                w.push_str("PRIMARY KEY ");
+                write_column_list(w, columns)?;
+            }
+            ConstraintKind::Unique(columns) => {
+                w.push_str("UNIQUE ");
+                write_column_list(w, columns)?;
+            }
+            ConstraintKind::Check(condition) => {
+                if condition.is_empty() {
+                    return Err(SqlBuilderError::EmptyCondition);
+                }
+                w.push_str("CHECK (");
+                condition.write_sql(w)?;
+                w.push(')');
+            }
+            ConstraintKind::ForeignKey(columns, references) => {
+                let count = references.column_count();
+                if count > 0 && count != columns.len() {
+                    return Err(SqlBuilderError::ColumnCountMismatch {
+                        expected: columns.len(),
+                        found: count,
+                    });
+                }
+                w.push_str("FOREIGN KEY ");
+                write_column_list(w, columns)?;
+                w.push(' ');
+                references.write_sql(w)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collects the given names into owned strings.
+fn to_strings<I, S>(columns: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    columns
+        .into_iter()
+        .map(|col| col.as_ref().to_string())
+        .collect()
+}
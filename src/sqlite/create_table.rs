@@ -1,12 +1,20 @@
-use super::{BuildableStatement, Column};
+use super::{
+    BuildableStatement, Column, ColumnType, Ident, Parser, Quoting, SqlWriter, TableConstraint,
+    WriteSql,
+};
 use crate::errors::SqlBuilderError;
 
 /// Represents the creation of a table with specified columns and options.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CreateTable {
     table: String,
     columns: Vec<Column>,
+    constraints: Vec<TableConstraint>,
     if_not_exists: bool,
+    temporary: bool,
+    strict: bool,
+    without_rowid: bool,
+    quoting: Option<Quoting>,
 }
 
 impl CreateTable {
@@ -24,18 +32,120 @@ impl CreateTable {
         Self {
             table: table.to_string(),
             columns,
+            constraints: Vec::new(),
             if_not_exists: false,
+            temporary: false,
+            strict: false,
+            without_rowid: false,
+            quoting: None,
         }
     }
 
+    /// Creates a new `CreateTable` instance from a SQL statement string.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::CreateTable;
+    ///
+    /// let query = "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL DEFAULT 'none');";
+    /// let create_table = CreateTable::from(query).unwrap();
+    /// assert_eq!(create_table.build().unwrap(), query);
+    /// ```
+    pub fn from(statement: &str) -> Result<CreateTable, SqlBuilderError> {
+        Parser::parse(statement, Parser::parse_create_table)
+    }
+
     /// Specifies that the table should be created only if it does not already exist.
     pub fn if_not_exists(mut self) -> Self {
         self.if_not_exists = true;
         self
     }
 
+    /// Adds a table constraint, rendered after the column definitions.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Column, CreateTable, Expr, References, TableConstraint};
+    ///
+    /// let create_table = CreateTable::new("orders", vec![
+    ///     Column::new("id").integer(),
+    ///     Column::new("user_id").integer(),
+    ///     Column::new("total").real(),
+    /// ])
+    /// .constraint(TableConstraint::primary_key(["id"]))
+    /// .constraint(TableConstraint::check(Expr::col("total").greater_than(0)).name("positive_total"))
+    /// .constraint(TableConstraint::foreign_key(["user_id"], References::new("users", ["id"])));
+    ///
+    /// assert_eq!(
+    ///     create_table.build().unwrap(),
+    ///     "CREATE TABLE orders (id INTEGER, user_id INTEGER, total REAL, PRIMARY KEY (id), \
+    ///      CONSTRAINT positive_total CHECK (total > 0), FOREIGN KEY (user_id) REFERENCES users (id));"
+    /// );
+    /// ```
+    pub fn constraint(mut self, constraint: TableConstraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Specifies that the table is temporary, visible only to the connection that creates it.
+    pub fn temporary(mut self) -> Self {
+        self.temporary = true;
+        self
+    }
+
+    /// Specifies that the table is `STRICT`, which rejects values that do not match the
+    /// column types. Every column must then be `INT`, `INTEGER`, `REAL`, `TEXT`, `BLOB` or `ANY`.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Column, CreateTable};
+    ///
+    /// let create_table = CreateTable::new("sessions", vec![
+    ///     Column::new("token").text().primary_key(),
+    ///     Column::new("data").any(),
+    /// ])
+    /// .temporary()
+    /// .strict()
+    /// .without_rowid();
+    ///
+    /// assert_eq!(
+    ///     create_table.build().unwrap(),
+    ///     "CREATE TEMP TABLE sessions (token TEXT PRIMARY KEY, data ANY) STRICT, WITHOUT ROWID;"
+    /// );
+    /// ```
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Specifies that the table is stored `WITHOUT ROWID`, which requires a primary key.
+    pub fn without_rowid(mut self) -> Self {
+        self.without_rowid = true;
+        self
+    }
+
+    /// Sets when the names of the statement are written between quotes, for names that do
+    /// not set their own policy.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Column, CreateTable, Quoting};
+    ///
+    /// let create_table = CreateTable::new("users", vec![Column::new("name").text()])
+    ///     .quoting(Quoting::Always);
+    /// assert_eq!(create_table.build().unwrap(), "CREATE TABLE \"users\" (\"name\" TEXT);");
+    ///
+    /// let create_table = CreateTable::new("users", vec![Column::new("first name").text()])
+    ///     .quoting(Quoting::Never);
+    /// assert!(create_table.build().is_err());
+    /// ```
+    pub fn quoting(mut self, quoting: Quoting) -> Self {
+        self.quoting = Some(quoting);
+        self
+    }
+
     /// Builds and returns the SQL statement for creating the table.
     pub fn build(&self) -> Result<String, SqlBuilderError> {
+        Ok(format!("{};", self.render()?))
+    }
+
+    fn write_statement(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
         if self.table.is_empty() {
             return Err(SqlBuilderError::EmptyTableName);
         }
@@ -44,19 +154,84 @@ impl CreateTable {
             return Err(SqlBuilderError::NoColumnsSpecified);
         }
 
-        let mut statement = if self.if_not_exists {
-            format!("CREATE TABLE IF NOT EXISTS {} (", self.table)
-        } else {
-            format!("CREATE TABLE {} (", self.table)
-        };
+        let primary_keys = self
+            .columns
+            .iter()
+            .filter(|col| col.is_primary_key())
+            .count()
+            + self
+                .constraints
+                .iter()
+                .filter(|c| c.is_primary_key())
+                .count();
+        if primary_keys > 1 || (self.without_rowid && primary_keys == 0) {
+            return Err(SqlBuilderError::InvalidQuery);
+        }
+
+        if self.strict {
+            let allowed = self.columns.iter().all(|col| {
+                let column_type = match col.column_type() {
+                    Some(ColumnType::Custom(name)) => Some(ColumnType::from_name(name)),
+                    column_type => column_type.cloned(),
+                };
+                matches!(
+                    column_type,
+                    Some(
+                        ColumnType::Int
+                            | ColumnType::Integer
+                            | ColumnType::Real
+                            | ColumnType::Text
+                            | ColumnType::Blob
+                            | ColumnType::Any
+                    )
+                )
+            });
+            if !allowed {
+                return Err(SqlBuilderError::InvalidColumnType);
+            }
+        }
 
-        let columns_sql: Result<Vec<String>, SqlBuilderError> =
-            self.columns.iter().map(|col| col.build()).collect();
+        w.push_str("CREATE ");
+        if self.temporary {
+            w.push_str("TEMP ");
+        }
+        w.push_str("TABLE ");
+        if self.if_not_exists {
+            w.push_str("IF NOT EXISTS ");
+        }
+        Ident::parse(&self.table).write_sql(w)?;
+        w.push_str(" (");
+
+        for (i, column) in self.columns.iter().enumerate() {
+            if i > 0 {
+                w.push_str(", ");
+            }
+            column.write_sql(w)?;
+        }
+        for constraint in &self.constraints {
+            w.push_str(", ");
+            constraint.write_sql(w)?;
+        }
+        w.push(')');
 
-        statement.push_str(&columns_sql?.join(", "));
-        statement.push_str(");");
+        let mut options = Vec::new();
+        if self.strict {
+            options.push("STRICT");
+        }
+        if self.without_rowid {
+            options.push("WITHOUT ROWID");
+        }
+        if !options.is_empty() {
+            w.push_str(&format!(" {}", options.join(", ")));
+        }
+
+        Ok(())
+    }
+}
 
-        Ok(statement)
+impl WriteSql for CreateTable {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        w.with_quoting(self.quoting, |w| self.write_statement(w))
     }
 }
 
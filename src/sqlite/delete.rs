@@ -1,11 +1,20 @@
-use super::BuildableStatement;
+use super::{
+    write_order, write_returning, write_table, write_with, BuildableStatement, Expr, IntoCondition,
+    OrderTerm, Parser, Placeholder, Quoting, ResultColumn, SqlWriter, Value, With, WriteSql,
+};
 use crate::errors::SqlBuilderError;
 
 /// Represents a ´DELETE´ clause builder for SQL queries
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Delete {
+    with: Option<With>,
     table: String,
-    condition: Option<String>,
+    condition: Option<Expr>,
+    returning: Vec<ResultColumn>,
+    order: Vec<OrderTerm>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+    quoting: Option<Quoting>,
 }
 
 impl Delete {
@@ -20,30 +29,165 @@ impl Delete {
     /// ```
     pub fn new(table: &str) -> Self {
         Self {
+            with: None,
             table: table.to_string(),
             condition: None,
+            returning: Vec::new(),
+            order: Vec::new(),
+            limit: None,
+            offset: None,
+            quoting: None,
         }
     }
 
+    /// Creates a new `Delete` instance from a SQL statement string.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Delete;
+    ///
+    /// let query = "DELETE FROM users WHERE age < 18 OR name IS NULL;";
+    /// let delete = Delete::from(query).unwrap();
+    /// assert_eq!(delete.build().unwrap(), query);
+    /// ```
+    pub fn from(statement: &str) -> Result<Delete, SqlBuilderError> {
+        Parser::parse(statement, Parser::parse_delete)
+    }
+
+    /// Prefixes the statement with a `WITH` clause.
+    pub fn with(&mut self, with: With) -> &mut Self {
+        self.with = Some(with);
+        self
+    }
+
     /// Specifies where for `Delete`.
-    pub fn condition(&mut self, condition: String) -> &mut Self {
-        self.condition = Some(condition);
+    pub fn condition(&mut self, condition: impl IntoCondition) -> &mut Self {
+        self.condition = Some(condition.into_condition());
+        self
+    }
+
+    /// Adds columns to the `RETURNING` clause, where a string names a column and
+    /// `ResultColumn::All` renders `*`.
+    pub fn returning<I, C>(&mut self, columns: I) -> &mut Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<ResultColumn>,
+    {
+        self.returning.extend(columns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Specifies the ordering of the rows a `LIMIT` applies to.
+    pub fn order(&mut self, order: &str) -> &mut Self {
+        self.order = vec![OrderTerm::new(Expr::raw(order))];
+        self
+    }
+
+    /// Adds terms to the ordering of the rows a `LIMIT` applies to.
+    pub fn order_by<I, T>(&mut self, order: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OrderTerm>,
+    {
+        self.order.extend(order.into_iter().map(Into::into));
+        self
+    }
+
+    /// Specifies the maximum number of rows to be deleted.
+    ///
+    /// Requires the `update-delete-limit` feature, as SQLite only accepts it when built with
+    /// `SQLITE_ENABLE_UPDATE_DELETE_LIMIT`.
+    pub fn limit(&mut self, limit: u32) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Specifies the number of rows skipped before the `LIMIT` applies.
+    pub fn offset(&mut self, offset: u32) -> &mut Self {
+        self.offset = Some(offset);
         self
     }
 
+    /// Sets when the names of the statement are written between quotes, for names that do
+    /// not set their own policy.
+    pub fn quoting(&mut self, quoting: Quoting) -> &mut Self {
+        self.quoting = Some(quoting);
+        self
+    }
+
+    /// Builds and returns the SQL statement for the `DELETE` query.
     pub fn build(&self) -> Result<String, SqlBuilderError> {
+        Ok(format!("{};", self.render()?))
+    }
+
+    /// Builds the SQL statement with placeholders and returns the values to bind.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Delete, Placeholder, Value, Where};
+    ///
+    /// let mut condition = Where::new();
+    /// condition.equal_to("age", "21");
+    ///
+    /// let (sql, params) = Delete::new("users_tb")
+    ///     .condition(condition)
+    ///     .build_with_params(Placeholder::Anonymous)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(sql, "DELETE FROM users_tb WHERE age = ?;");
+    /// assert_eq!(params, vec![Value::from("21")]);
+    /// ```
+    pub fn build_with_params(
+        &self,
+        placeholder: Placeholder,
+    ) -> Result<(String, Vec<Value>), SqlBuilderError> {
+        let (sql, params) = self.render_with_params(placeholder)?;
+        Ok((format!("{};", sql), params))
+    }
+
+    /// Internal method to write the statement under the quoting policy set on the writer.
+    fn write_statement(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
         if self.table.is_empty() {
             return Err(SqlBuilderError::EmptyTableName);
         }
 
+        if !self.order.is_empty() || self.limit.is_some() || self.offset.is_some() {
+            if !cfg!(feature = "update-delete-limit") {
+                return Err(SqlBuilderError::UpdateDeleteLimitDisabled);
+            }
+            if self.limit.is_none() {
+                return Err(SqlBuilderError::InvalidQuery);
+            }
+        }
+
+        write_with(w, &self.with)?;
+        w.push_str("DELETE FROM ");
+        write_table(w, &self.table)?;
+
         if let Some(condition) = &self.condition {
             if condition.is_empty() {
                 return Err(SqlBuilderError::EmptyCondition);
             }
-            return Ok(format!("DELETE FROM {} WHERE {};", self.table, condition));
+            w.push_str(" WHERE ");
+            condition.write_sql(w)?;
         }
 
-        Ok(format!("DELETE FROM {};", self.table))
+        write_returning(w, &self.returning)?;
+        write_order(w, &self.order)?;
+
+        if let Some(limit) = &self.limit {
+            w.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        if let Some(offset) = &self.offset {
+            w.push_str(&format!(" OFFSET {}", offset));
+        }
+
+        Ok(())
+    }
+}
+
+impl WriteSql for Delete {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        w.with_quoting(self.quoting, |w| self.write_statement(w))
     }
 }
 
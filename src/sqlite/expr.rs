@@ -0,0 +1,922 @@
+use super::{
+    check_arguments, BuildableStatement, Compound, Ident, OrderTerm, Over, Parser, Select,
+    SqlWriter, Value, Window, WriteSql,
+};
+use crate::errors::SqlBuilderError;
+
+/// Represents the binary operators available in expressions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Or,
+    And,
+    Equal,
+    NotEqual,
+    Is,
+    IsNot,
+    Like,
+    NotLike,
+    Glob,
+    NotGlob,
+    LessThan,
+    LessThanEqual,
+    GreaterThan,
+    GreaterThanEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Concat,
+    BitAnd,
+    BitOr,
+    ShiftLeft,
+    ShiftRight,
+}
+
+impl BinaryOp {
+    /// Returns the binding strength of the operator, following SQLite's precedence table.
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Or => OR,
+            Self::And => AND,
+            Self::Equal
+            | Self::NotEqual
+            | Self::Is
+            | Self::IsNot
+            | Self::Like
+            | Self::NotLike
+            | Self::Glob
+            | Self::NotGlob => EQUALITY,
+            Self::LessThan | Self::LessThanEqual | Self::GreaterThan | Self::GreaterThanEqual => {
+                RELATIONAL
+            }
+            Self::Add | Self::Subtract => ADDITIVE,
+            Self::Multiply | Self::Divide | Self::Modulo => MULTIPLICATIVE,
+            Self::Concat => CONCAT,
+            Self::BitAnd | Self::BitOr | Self::ShiftLeft | Self::ShiftRight => BITWISE,
+        }
+    }
+}
+
+/// Implementation of the `BuildableStatement` trait for `BinaryOp`, allowing it to be printed.
+impl BuildableStatement for BinaryOp {
+    fn build(&self) -> String {
+        String::from(match self {
+            Self::Or => "OR",
+            Self::And => "AND",
+            Self::Equal => "=",
+            Self::NotEqual => "!=",
+            Self::Is => "IS",
+            Self::IsNot => "IS NOT",
+            Self::Like => "LIKE",
+            Self::NotLike => "NOT LIKE",
+            Self::Glob => "GLOB",
+            Self::NotGlob => "NOT GLOB",
+            Self::LessThan => "<",
+            Self::LessThanEqual => "<=",
+            Self::GreaterThan => ">",
+            Self::GreaterThanEqual => ">=",
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Modulo => "%",
+            Self::Concat => "||",
+            Self::BitAnd => "&",
+            Self::BitOr => "|",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+        })
+    }
+}
+
+/// Represents the unary operators available in expressions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOp {
+    Not,
+    Negate,
+    BitNot,
+}
+
+/// Raw SQL is opaque, so it binds looser than any operator and is parenthesized as an operand.
+const RAW: u8 = 0;
+const OR: u8 = 1;
+const AND: u8 = 2;
+const NOT: u8 = 3;
+const EQUALITY: u8 = 4;
+const RELATIONAL: u8 = 5;
+const BITWISE: u8 = 6;
+const ADDITIVE: u8 = 7;
+const MULTIPLICATIVE: u8 = 8;
+const CONCAT: u8 = 9;
+const UNARY: u8 = 10;
+const PRIMARY: u8 = 11;
+
+/// Represents a SQL expression as a tree, rendered with parentheses only where precedence requires them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// A literal value, escaped or bound as a parameter.
+    Value(Value),
+    /// A column reference such as `name` or `u.name`.
+    Column(Ident),
+    /// Raw SQL rendered verbatim and never parenthesized.
+    Raw(String),
+    /// An explicitly parenthesized expression.
+    ///
+    /// The constructors drop this wrapper where precedence already forces parentheses,
+    /// so a tree built in code and the same tree parsed back from SQL compare equal.
+    Nested(Box<Expr>),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    IsNull {
+        expr: Box<Expr>,
+        negated: bool,
+    },
+    InList {
+        expr: Box<Expr>,
+        list: Vec<Expr>,
+        negated: bool,
+    },
+    Between {
+        expr: Box<Expr>,
+        low: Box<Expr>,
+        high: Box<Expr>,
+        negated: bool,
+    },
+    Function {
+        name: String,
+        args: Vec<Expr>,
+        distinct: bool,
+    },
+    /// A `CAST(expr AS type)` conversion.
+    Cast {
+        expr: Box<Expr>,
+        type_name: String,
+    },
+    Case(Case),
+    /// A parenthesized subquery used as a scalar value.
+    Subquery(Box<Compound>),
+    /// An `IN (SELECT ...)` test.
+    InSubquery {
+        expr: Box<Expr>,
+        subquery: Box<Compound>,
+        negated: bool,
+    },
+    /// A window function call, such as `row_number() OVER (ORDER BY id)`.
+    Over {
+        function: Box<Expr>,
+        over: Box<Over>,
+    },
+    /// An `EXISTS (SELECT ...)` test.
+    Exists {
+        subquery: Box<Compound>,
+        negated: bool,
+    },
+}
+
+impl Expr {
+    /// Creates a column reference.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Expr;
+    ///
+    /// let expr = Expr::col("age").greater_than(18).and(Expr::col("active").equal_to(true));
+    /// assert_eq!(expr.build().unwrap(), "age > 18 AND active = TRUE");
+    /// ```
+    pub fn col(name: &str) -> Self {
+        Self::Column(Ident::parse(name))
+    }
+
+    /// Creates a reference to the value an upsert tried to insert, as `excluded.name`.
+    pub fn excluded(name: &str) -> Self {
+        Self::Column(Ident::parse(&format!("excluded.{}", name)))
+    }
+
+    /// Creates a literal value.
+    pub fn value(value: impl Into<Value>) -> Self {
+        Self::Value(value.into())
+    }
+
+    /// Creates a raw SQL fragment that is rendered verbatim.
+    pub fn raw(sql: &str) -> Self {
+        Self::Raw(sql.to_string())
+    }
+
+    /// Creates a function call such as `lower(name)`.
+    pub fn func(name: &str, args: Vec<Expr>) -> Self {
+        Self::Function {
+            name: name.to_string(),
+            args,
+            distinct: false,
+        }
+    }
+
+    /// Creates a function call over distinct arguments such as `count(DISTINCT city)`.
+    pub fn func_distinct(name: &str, args: Vec<Expr>) -> Self {
+        Self::Function {
+            name: name.to_string(),
+            args,
+            distinct: true,
+        }
+    }
+
+    /// Creates a `CAST(self AS type_name)` conversion.
+    pub fn cast(self, type_name: &str) -> Self {
+        Self::Cast {
+            expr: Box::new(self),
+            type_name: type_name.to_string(),
+        }
+    }
+
+    /// Creates a scalar subquery from a `Select` or `Compound`.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Select};
+    ///
+    /// let mut average = Select::new("users");
+    /// average.columns("avg(age)");
+    ///
+    /// let expr = Expr::col("age").greater_than(Expr::subquery(average));
+    /// assert_eq!(expr.build().unwrap(), "age > (SELECT avg(age) FROM users)");
+    /// ```
+    pub fn subquery(query: impl Into<Compound>) -> Self {
+        Self::Subquery(Box::new(query.into()))
+    }
+
+    /// Creates an ascending `ORDER BY` term over the expression.
+    pub fn asc(self) -> OrderTerm {
+        OrderTerm::new(self).asc()
+    }
+
+    /// Creates a descending `ORDER BY` term over the expression.
+    pub fn desc(self) -> OrderTerm {
+        OrderTerm::new(self).desc()
+    }
+
+    /// Evaluates the function as a window function over the given window definition.
+    pub fn over(self, window: Window) -> Self {
+        Self::Over {
+            function: Box::new(self),
+            over: Box::new(Over::Window(window)),
+        }
+    }
+
+    /// Evaluates the function as a window function over a named window of the `Select`.
+    pub fn over_named(self, name: &str) -> Self {
+        Self::Over {
+            function: Box::new(self),
+            over: Box::new(Over::Named(name.to_string())),
+        }
+    }
+
+    /// Creates an `EXISTS (SELECT ...)` test.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Select};
+    ///
+    /// let mut orders = Select::new("orders");
+    /// orders.condition(Expr::col("orders.user_id").equal_to(Expr::col("users.id")));
+    ///
+    /// assert_eq!(
+    ///     Expr::not_exists(orders).build().unwrap(),
+    ///     "NOT EXISTS (SELECT * FROM orders WHERE orders.user_id = users.id)"
+    /// );
+    /// ```
+    pub fn exists(query: impl Into<Compound>) -> Self {
+        Self::Exists {
+            subquery: Box::new(query.into()),
+            negated: false,
+        }
+    }
+
+    /// Creates a `NOT EXISTS (SELECT ...)` test.
+    pub fn not_exists(query: impl Into<Compound>) -> Self {
+        Self::Exists {
+            subquery: Box::new(query.into()),
+            negated: true,
+        }
+    }
+
+    /// Wraps the expression in explicit parentheses.
+    pub fn nested(self) -> Self {
+        Self::Nested(Box::new(self))
+    }
+
+    /// Creates a binary operation between two expressions.
+    pub fn binary(self, op: BinaryOp, rhs: impl Into<Expr>) -> Self {
+        let precedence = op.precedence();
+        Self::Binary(
+            self.operand(precedence),
+            op,
+            rhs.into().operand(precedence + 1),
+        )
+    }
+
+    /// Creates a unary operation on an expression.
+    pub fn unary(op: UnaryOp, expr: impl Into<Expr>) -> Self {
+        let precedence = match op {
+            UnaryOp::Not => NOT,
+            UnaryOp::Negate | UnaryOp::BitNot => PRIMARY,
+        };
+        Self::Unary(op, expr.into().operand(precedence))
+    }
+
+    /// Boxes the expression as an operand, dropping explicit parentheses that
+    /// rendering would add anyway because it binds looser than `min`.
+    fn operand(self, min: u8) -> Box<Expr> {
+        match self {
+            Self::Nested(inner) if inner.precedence() < min => inner,
+            expr => Box::new(expr),
+        }
+    }
+
+    /// Combines two expressions with `AND`.
+    pub fn and(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::And, rhs)
+    }
+
+    /// Combines two expressions with `OR`.
+    pub fn or(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::Or, rhs)
+    }
+
+    /// Creates an equality comparison (`self = rhs`).
+    pub fn equal_to(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::Equal, rhs)
+    }
+
+    /// Creates a not equal comparison (`self != rhs`).
+    pub fn not_equal_to(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::NotEqual, rhs)
+    }
+
+    /// Creates a greater than comparison (`self > rhs`).
+    pub fn greater_than(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::GreaterThan, rhs)
+    }
+
+    /// Creates a greater than or equal comparison (`self >= rhs`).
+    pub fn greater_than_equal(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::GreaterThanEqual, rhs)
+    }
+
+    /// Creates a less than comparison (`self < rhs`).
+    pub fn less_than(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::LessThan, rhs)
+    }
+
+    /// Creates a less than or equal comparison (`self <= rhs`).
+    pub fn less_than_equal(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::LessThanEqual, rhs)
+    }
+
+    /// Creates an `IS` comparison (`self IS rhs`).
+    pub fn is(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::Is, rhs)
+    }
+
+    /// Creates an `IS NOT` comparison (`self IS NOT rhs`).
+    pub fn is_not(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::IsNot, rhs)
+    }
+
+    /// Creates a `LIKE` comparison (`self LIKE rhs`).
+    pub fn like(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::Like, rhs)
+    }
+
+    /// Creates a `NOT LIKE` comparison (`self NOT LIKE rhs`).
+    pub fn not_like(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::NotLike, rhs)
+    }
+
+    /// Creates a `GLOB` comparison (`self GLOB rhs`).
+    pub fn glob(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::Glob, rhs)
+    }
+
+    /// Creates a `NOT GLOB` comparison (`self NOT GLOB rhs`).
+    pub fn not_glob(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::NotGlob, rhs)
+    }
+
+    /// Creates a string concatenation (`self || rhs`).
+    pub fn concat(self, rhs: impl Into<Expr>) -> Self {
+        self.binary(BinaryOp::Concat, rhs)
+    }
+
+    /// Creates an `IS NULL` test.
+    pub fn is_null(self) -> Self {
+        Self::IsNull {
+            expr: self.operand(EQUALITY),
+            negated: false,
+        }
+    }
+
+    /// Creates an `IS NOT NULL` test.
+    pub fn is_not_null(self) -> Self {
+        Self::IsNull {
+            expr: self.operand(EQUALITY),
+            negated: true,
+        }
+    }
+
+    /// Creates an `IN` test against a list of expressions.
+    pub fn inside<T: Into<Expr>>(self, list: Vec<T>) -> Self {
+        Self::InList {
+            expr: self.operand(EQUALITY),
+            list: list.into_iter().map(Into::into).collect(),
+            negated: false,
+        }
+    }
+
+    /// Creates a `NOT IN` test against a list of expressions.
+    pub fn not_inside<T: Into<Expr>>(self, list: Vec<T>) -> Self {
+        Self::InList {
+            expr: self.operand(EQUALITY),
+            list: list.into_iter().map(Into::into).collect(),
+            negated: true,
+        }
+    }
+
+    /// Creates an `IN (SELECT ...)` test against the rows of a subquery.
+    pub fn in_subquery(self, query: impl Into<Compound>) -> Self {
+        Self::InSubquery {
+            expr: self.operand(EQUALITY),
+            subquery: Box::new(query.into()),
+            negated: false,
+        }
+    }
+
+    /// Creates a `NOT IN (SELECT ...)` test against the rows of a subquery.
+    pub fn not_in_subquery(self, query: impl Into<Compound>) -> Self {
+        Self::InSubquery {
+            expr: self.operand(EQUALITY),
+            subquery: Box::new(query.into()),
+            negated: true,
+        }
+    }
+
+    /// Creates a `BETWEEN low AND high` test.
+    pub fn between(self, low: impl Into<Expr>, high: impl Into<Expr>) -> Self {
+        Self::Between {
+            expr: self.operand(EQUALITY),
+            low: low.into().operand(RELATIONAL),
+            high: high.into().operand(RELATIONAL),
+            negated: false,
+        }
+    }
+
+    /// Creates a `NOT BETWEEN low AND high` test.
+    pub fn not_between(self, low: impl Into<Expr>, high: impl Into<Expr>) -> Self {
+        Self::Between {
+            expr: self.operand(EQUALITY),
+            low: low.into().operand(RELATIONAL),
+            high: high.into().operand(RELATIONAL),
+            negated: true,
+        }
+    }
+
+    /// Builds and returns the SQL representation of the expression.
+    pub fn build(&self) -> Result<String, SqlBuilderError> {
+        self.render()
+    }
+
+    /// Returns `true` if the expression is raw SQL with no content.
+    pub(crate) fn is_empty(&self) -> bool {
+        matches!(self, Self::Raw(s) if s.trim().is_empty())
+    }
+
+    /// Returns `true` if the expression calls an aggregate function outside of a subquery.
+    /// Raw SQL is parsed to look for one.
+    pub(crate) fn has_aggregate(&self) -> bool {
+        match self {
+            Self::Function { name, args, .. } => {
+                let aggregate = match name.to_ascii_lowercase().as_str() {
+                    "count" | "sum" | "avg" | "total" | "group_concat" | "string_agg" => true,
+                    "min" | "max" => args.len() == 1,
+                    _ => false,
+                };
+                aggregate || args.iter().any(Self::has_aggregate)
+            }
+            Self::Raw(sql) => Parser::parse(sql, Parser::parse_expr)
+                .is_ok_and(|expr| !matches!(expr, Self::Raw(_)) && expr.has_aggregate()),
+            Self::Nested(expr)
+            | Self::Unary(_, expr)
+            | Self::IsNull { expr, .. }
+            | Self::Cast { expr, .. }
+            | Self::InSubquery { expr, .. } => expr.has_aggregate(),
+            Self::Binary(lhs, _, rhs) => lhs.has_aggregate() || rhs.has_aggregate(),
+            Self::InList { expr, list, .. } => {
+                expr.has_aggregate() || list.iter().any(Self::has_aggregate)
+            }
+            Self::Between {
+                expr, low, high, ..
+            } => expr.has_aggregate() || low.has_aggregate() || high.has_aggregate(),
+            Self::Case(case) => {
+                case.operand.iter().any(|e| e.has_aggregate())
+                    || case
+                        .branches
+                        .iter()
+                        .any(|(when, then)| when.has_aggregate() || then.has_aggregate())
+                    || case.otherwise.iter().any(|e| e.has_aggregate())
+            }
+            Self::Value(_)
+            | Self::Column(_)
+            | Self::Subquery(_)
+            | Self::Over { .. }
+            | Self::Exists { .. } => false,
+        }
+    }
+
+    /// Returns the binding strength of the expression's outermost operator.
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Binary(_, op, _) => op.precedence(),
+            Self::Unary(UnaryOp::Not, _) => NOT,
+            Self::Unary(UnaryOp::Negate | UnaryOp::BitNot, _) => UNARY,
+            Self::IsNull { .. }
+            | Self::InList { .. }
+            | Self::InSubquery { .. }
+            | Self::Between { .. } => EQUALITY,
+            Self::Exists { negated: true, .. } => NOT,
+            Self::Raw(_) => RAW,
+            Self::Value(Value::Integer(i)) if *i < 0 => UNARY,
+            Self::Value(Value::Real(r)) if r.is_sign_negative() => UNARY,
+            _ => PRIMARY,
+        }
+    }
+
+    /// Writes the expression, parenthesizing it if it binds looser than `min`.
+    fn write_operand(&self, w: &mut SqlWriter, min: u8) -> Result<(), SqlBuilderError> {
+        if self.precedence() < min {
+            w.push('(');
+            self.write_sql(w)?;
+            w.push(')');
+            Ok(())
+        } else {
+            self.write_sql(w)
+        }
+    }
+}
+
+impl WriteSql for Expr {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        match self {
+            Self::Value(v) => w.push_value(v),
+            Self::Column(name) => name.write_sql(w)?,
+            Self::Raw(sql) => w.push_str(sql),
+            Self::Nested(expr) => {
+                w.push('(');
+                expr.write_sql(w)?;
+                w.push(')');
+            }
+            Self::Unary(UnaryOp::Not, expr) => {
+                w.push_str("NOT ");
+                expr.write_operand(w, NOT)?;
+            }
+            Self::Unary(UnaryOp::Negate, expr) => {
+                w.push('-');
+                expr.write_operand(w, PRIMARY)?;
+            }
+            Self::Unary(UnaryOp::BitNot, expr) => {
+                w.push('~');
+                expr.write_operand(w, PRIMARY)?;
+            }
+            Self::Binary(lhs, op, rhs) => {
+                let precedence = op.precedence();
+                lhs.write_operand(w, precedence)?;
+                w.push_str(&format!(" {} ", op.build()));
+                rhs.write_operand(w, precedence + 1)?;
+            }
+            Self::IsNull { expr, negated } => {
+                expr.write_operand(w, EQUALITY)?;
+                w.push_str(if *negated { " IS NOT NULL" } else { " IS NULL" });
+            }
+            Self::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                expr.write_operand(w, EQUALITY)?;
+                w.push_str(if *negated { " NOT IN (" } else { " IN (" });
+                write_list(w, list)?;
+                w.push(')');
+            }
+            Self::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => {
+                expr.write_operand(w, EQUALITY)?;
+                w.push_str(if *negated {
+                    " NOT BETWEEN "
+                } else {
+                    " BETWEEN "
+                });
+                low.write_operand(w, RELATIONAL)?;
+                w.push_str(" AND ");
+                high.write_operand(w, RELATIONAL)?;
+            }
+            Self::Function {
+                name,
+                args,
+                distinct,
+            } => {
+                if name.is_empty() {
+                    return Err(SqlBuilderError::InvalidQuery);
+                }
+                check_arguments(name, args.len())?;
+                w.push_str(name);
+                w.push_str(if *distinct { "(DISTINCT " } else { "(" });
+                write_list(w, args)?;
+                w.push(')');
+            }
+            Self::Cast { expr, type_name } => {
+                w.push_str("CAST(");
+                expr.write_sql(w)?;
+                w.push_str(&format!(" AS {})", type_name));
+            }
+            Self::Case(case) => case.write_sql(w)?,
+            Self::Subquery(query) => {
+                w.push('(');
+                query.write_sql(w)?;
+                w.push(')');
+            }
+            Self::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                expr.write_operand(w, EQUALITY)?;
+                w.push_str(if *negated { " NOT IN (" } else { " IN (" });
+                subquery.write_sql(w)?;
+                w.push(')');
+            }
+            Self::Over { function, over } => {
+                if !matches!(**function, Self::Function { .. }) {
+                    return Err(SqlBuilderError::InvalidQuery);
+                }
+                function.write_sql(w)?;
+                w.push_str(" OVER ");
+                over.write_sql(w)?;
+            }
+            Self::Exists { subquery, negated } => {
+                w.push_str(if *negated { "NOT EXISTS (" } else { "EXISTS (" });
+                subquery.write_sql(w)?;
+                w.push(')');
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a comma-separated list of expressions.
+pub(crate) fn write_list(w: &mut SqlWriter, list: &[Expr]) -> Result<(), SqlBuilderError> {
+    for (i, expr) in list.iter().enumerate() {
+        if i > 0 {
+            w.push_str(", ");
+        }
+        expr.write_sql(w)?;
+    }
+    Ok(())
+}
+
+/// Represents a `CASE` expression, with or without a base operand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    operand: Option<Box<Expr>>,
+    branches: Vec<(Expr, Expr)>,
+    otherwise: Option<Box<Expr>>,
+}
+
+impl Case {
+    /// Creates a searched `CASE WHEN ... THEN ... END` expression.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Case, Expr};
+    ///
+    /// let case = Case::new()
+    ///     .when(Expr::col("age").less_than(18), Expr::value("minor"))
+    ///     .otherwise(Expr::value("adult"));
+    ///
+    /// assert_eq!(
+    ///     Expr::from(case).build().unwrap(),
+    ///     "CASE WHEN age < 18 THEN 'minor' ELSE 'adult' END"
+    /// );
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            operand: None,
+            branches: Vec::new(),
+            otherwise: None,
+        }
+    }
+
+    /// Creates a simple `CASE operand WHEN ... THEN ... END` expression.
+    pub fn on(operand: impl Into<Expr>) -> Self {
+        Self {
+            operand: Some(Box::new(operand.into())),
+            branches: Vec::new(),
+            otherwise: None,
+        }
+    }
+
+    /// Adds a `WHEN condition THEN result` branch.
+    pub fn when(mut self, condition: impl Into<Expr>, result: impl Into<Expr>) -> Self {
+        self.branches.push((condition.into(), result.into()));
+        self
+    }
+
+    /// Specifies the `ELSE` result.
+    pub fn otherwise(mut self, result: impl Into<Expr>) -> Self {
+        self.otherwise = Some(Box::new(result.into()));
+        self
+    }
+}
+
+impl Default for Case {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WriteSql for Case {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        if self.branches.is_empty() {
+            return Err(SqlBuilderError::EmptyCondition);
+        }
+
+        w.push_str("CASE");
+        if let Some(operand) = &self.operand {
+            w.push(' ');
+            operand.write_sql(w)?;
+        }
+        for (condition, result) in &self.branches {
+            w.push_str(" WHEN ");
+            condition.write_sql(w)?;
+            w.push_str(" THEN ");
+            result.write_sql(w)?;
+        }
+        if let Some(otherwise) = &self.otherwise {
+            w.push_str(" ELSE ");
+            otherwise.write_sql(w)?;
+        }
+        w.push_str(" END");
+        Ok(())
+    }
+}
+
+impl From<Ident> for Expr {
+    fn from(ident: Ident) -> Self {
+        Self::Column(ident)
+    }
+}
+
+impl From<Case> for Expr {
+    fn from(case: Case) -> Self {
+        Self::Case(case)
+    }
+}
+
+impl From<Value> for Expr {
+    fn from(value: Value) -> Self {
+        Self::Value(value)
+    }
+}
+
+/// Strings are treated as text values; raw SQL must be opted into with `Expr::raw`.
+impl From<String> for Expr {
+    fn from(text: String) -> Self {
+        Self::Value(Value::Text(text))
+    }
+}
+
+/// Strings are treated as text values; raw SQL must be opted into with `Expr::raw`.
+impl From<&str> for Expr {
+    fn from(text: &str) -> Self {
+        Self::Value(Value::Text(text.to_string()))
+    }
+}
+
+impl From<Select> for Expr {
+    fn from(select: Select) -> Self {
+        Self::subquery(select)
+    }
+}
+
+impl From<Compound> for Expr {
+    fn from(compound: Compound) -> Self {
+        Self::subquery(compound)
+    }
+}
+
+macro_rules! impl_from_value {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Expr {
+                fn from(value: $t) -> Self {
+                    Self::Value(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value!(i8, i16, i32, i64, u8, u16, u32, f32, f64, bool);
+
+/// Converts an item of a column list, such as a result column or an `ORDER BY` term, into
+/// an expression. Unlike `Into<Expr>`, a string names a column, which may be qualified or
+/// quoted; anything that is not a name is reported when the statement is built.
+/// # Example
+/// ```
+/// use lumus_sql_builder::sqlite::Select;
+///
+/// let mut select = Select::new("users");
+/// select.columns(["name", "u.\"first name\""]).order_by(["name"]);
+/// assert_eq!(
+///     select.build().unwrap(),
+///     "SELECT name, u.\"first name\" FROM users ORDER BY name;"
+/// );
+///
+/// let mut select = Select::new("users");
+/// select.columns(["lower(name)"]);
+/// assert!(select.build().is_err());
+/// ```
+pub trait IntoColumnExpr {
+    fn into_column_expr(self) -> Expr;
+}
+
+impl IntoColumnExpr for &str {
+    fn into_column_expr(self) -> Expr {
+        Expr::Column(Ident::strict(self))
+    }
+}
+
+impl IntoColumnExpr for &String {
+    fn into_column_expr(self) -> Expr {
+        self.as_str().into_column_expr()
+    }
+}
+
+impl IntoColumnExpr for String {
+    fn into_column_expr(self) -> Expr {
+        self.as_str().into_column_expr()
+    }
+}
+
+macro_rules! impl_into_column_expr {
+    ($($t:ty),*) => {
+        $(
+            impl IntoColumnExpr for $t {
+                fn into_column_expr(self) -> Expr {
+                    self.into()
+                }
+            }
+        )*
+    };
+}
+
+impl_into_column_expr!(
+    Expr, Ident, Case, Value, Select, Compound, i8, i16, i32, i64, u8, u16, u32, f32, f64, bool
+);
+
+impl core::ops::Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Expr {
+        Expr::unary(UnaryOp::Not, self)
+    }
+}
+
+impl core::ops::Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        Expr::unary(UnaryOp::Negate, self)
+    }
+}
+
+macro_rules! impl_arithmetic {
+    ($($trait:ident, $method:ident, $op:ident);*) => {
+        $(
+            impl<T: Into<Expr>> core::ops::$trait<T> for Expr {
+                type Output = Expr;
+
+                fn $method(self, rhs: T) -> Expr {
+                    self.binary(BinaryOp::$op, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_arithmetic!(
+    Add, add, Add;
+    Sub, sub, Subtract;
+    Mul, mul, Multiply;
+    Div, div, Divide;
+    Rem, rem, Modulo
+);
@@ -0,0 +1,397 @@
+use super::Expr;
+use crate::errors::SqlBuilderError;
+
+/// The number of arguments accepted by SQLite's built-in functions, as `(name, min, max)`.
+/// `None` means the function accepts any number of arguments from `min` on.
+const BUILTINS: [(&str, usize, Option<usize>); 70] = [
+    ("abs", 1, Some(1)),
+    ("acos", 1, Some(1)),
+    ("acosh", 1, Some(1)),
+    ("asin", 1, Some(1)),
+    ("asinh", 1, Some(1)),
+    ("atan", 1, Some(1)),
+    ("atan2", 2, Some(2)),
+    ("atanh", 1, Some(1)),
+    ("avg", 1, Some(1)),
+    ("ceil", 1, Some(1)),
+    ("ceiling", 1, Some(1)),
+    ("coalesce", 2, None),
+    ("cos", 1, Some(1)),
+    ("cosh", 1, Some(1)),
+    ("count", 0, Some(1)),
+    ("cume_dist", 0, Some(0)),
+    ("date", 0, None),
+    ("datetime", 0, None),
+    ("degrees", 1, Some(1)),
+    ("dense_rank", 0, Some(0)),
+    ("exp", 1, Some(1)),
+    ("first_value", 1, Some(1)),
+    ("floor", 1, Some(1)),
+    ("group_concat", 1, Some(2)),
+    ("ifnull", 2, Some(2)),
+    ("iif", 3, Some(3)),
+    ("instr", 2, Some(2)),
+    ("julianday", 0, None),
+    ("lag", 1, Some(3)),
+    ("last_value", 1, Some(1)),
+    ("lead", 1, Some(3)),
+    ("length", 1, Some(1)),
+    ("ln", 1, Some(1)),
+    ("log", 1, Some(2)),
+    ("log10", 1, Some(1)),
+    ("log2", 1, Some(1)),
+    ("lower", 1, Some(1)),
+    ("ltrim", 1, Some(2)),
+    ("max", 1, None),
+    ("min", 1, None),
+    ("mod", 2, Some(2)),
+    ("nth_value", 2, Some(2)),
+    ("ntile", 1, Some(1)),
+    ("nullif", 2, Some(2)),
+    ("percent_rank", 0, Some(0)),
+    ("pi", 0, Some(0)),
+    ("pow", 2, Some(2)),
+    ("power", 2, Some(2)),
+    ("radians", 1, Some(1)),
+    ("rank", 0, Some(0)),
+    ("replace", 3, Some(3)),
+    ("round", 1, Some(2)),
+    ("row_number", 0, Some(0)),
+    ("rtrim", 1, Some(2)),
+    ("sign", 1, Some(1)),
+    ("sin", 1, Some(1)),
+    ("sinh", 1, Some(1)),
+    ("sqrt", 1, Some(1)),
+    ("strftime", 1, None),
+    ("string_agg", 2, Some(2)),
+    ("substr", 2, Some(3)),
+    ("substring", 2, Some(3)),
+    ("sum", 1, Some(1)),
+    ("tan", 1, Some(1)),
+    ("tanh", 1, Some(1)),
+    ("time", 0, None),
+    ("total", 1, Some(1)),
+    ("trim", 1, Some(2)),
+    ("trunc", 1, Some(1)),
+    ("upper", 1, Some(1)),
+];
+
+/// Checks the number of arguments of a call to one of SQLite's built-in functions.
+/// Calls to other functions are not checked.
+pub(crate) fn check_arguments(name: &str, count: usize) -> Result<(), SqlBuilderError> {
+    let name = name.to_ascii_lowercase();
+    let Some((_, min, max)) = BUILTINS.iter().find(|(builtin, ..)| *builtin == name) else {
+        return Ok(());
+    };
+    if count < *min || max.is_some_and(|max| count > max) {
+        return Err(SqlBuilderError::InvalidArgumentCount {
+            function: name,
+            found: count,
+        });
+    }
+    Ok(())
+}
+
+/// Helpers for SQLite's aggregate functions.
+impl Expr {
+    /// Creates a `count(expr)` call.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Select};
+    ///
+    /// let mut select = Select::new("users");
+    /// select
+    ///     .column_as(Expr::count_all(), "total")
+    ///     .column_as(Expr::count_distinct(Expr::col("city")), "cities")
+    ///     .column_as(Expr::avg(Expr::col("age")), "average");
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "SELECT count(*) AS total, count(DISTINCT city) AS cities, avg(age) AS average FROM users;"
+    /// );
+    /// ```
+    pub fn count(expr: impl Into<Expr>) -> Self {
+        Self::func("count", vec![expr.into()])
+    }
+
+    /// Creates a `count(*)` call.
+    pub fn count_all() -> Self {
+        Self::func("count", vec![Self::raw("*")])
+    }
+
+    /// Creates a `count(DISTINCT expr)` call.
+    pub fn count_distinct(expr: impl Into<Expr>) -> Self {
+        Self::func_distinct("count", vec![expr.into()])
+    }
+
+    /// Creates a `sum(expr)` call.
+    pub fn sum(expr: impl Into<Expr>) -> Self {
+        Self::func("sum", vec![expr.into()])
+    }
+
+    /// Creates a `total(expr)` call, which returns `0.0` rather than `NULL` for no rows.
+    pub fn total(expr: impl Into<Expr>) -> Self {
+        Self::func("total", vec![expr.into()])
+    }
+
+    /// Creates an `avg(expr)` call.
+    pub fn avg(expr: impl Into<Expr>) -> Self {
+        Self::func("avg", vec![expr.into()])
+    }
+
+    /// Creates a `min(...)` call: the aggregate with one argument, the scalar with more.
+    pub fn min(args: Vec<Expr>) -> Self {
+        Self::func("min", args)
+    }
+
+    /// Creates a `max(...)` call: the aggregate with one argument, the scalar with more.
+    pub fn max(args: Vec<Expr>) -> Self {
+        Self::func("max", args)
+    }
+
+    /// Creates a `group_concat(expr [, separator])` call.
+    pub fn group_concat(args: Vec<Expr>) -> Self {
+        Self::func("group_concat", args)
+    }
+}
+
+/// Helpers for SQLite's core scalar functions.
+impl Expr {
+    /// Creates a `coalesce(...)` call, which takes at least two arguments.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Expr;
+    ///
+    /// let name = Expr::coalesce(vec![Expr::col("nickname"), Expr::col("name")]);
+    /// assert_eq!(name.build().unwrap(), "coalesce(nickname, name)");
+    ///
+    /// let invalid = Expr::coalesce(vec![Expr::col("name")]);
+    /// assert!(invalid.build().is_err());
+    /// ```
+    pub fn coalesce(args: Vec<Expr>) -> Self {
+        Self::func("coalesce", args)
+    }
+
+    /// Creates an `ifnull(expr, fallback)` call.
+    pub fn ifnull(expr: impl Into<Expr>, fallback: impl Into<Expr>) -> Self {
+        Self::func("ifnull", vec![expr.into(), fallback.into()])
+    }
+
+    /// Creates a `nullif(lhs, rhs)` call.
+    pub fn nullif(lhs: impl Into<Expr>, rhs: impl Into<Expr>) -> Self {
+        Self::func("nullif", vec![lhs.into(), rhs.into()])
+    }
+
+    /// Creates a `length(expr)` call.
+    pub fn length(expr: impl Into<Expr>) -> Self {
+        Self::func("length", vec![expr.into()])
+    }
+
+    /// Creates a `lower(expr)` call.
+    pub fn lower(expr: impl Into<Expr>) -> Self {
+        Self::func("lower", vec![expr.into()])
+    }
+
+    /// Creates an `upper(expr)` call.
+    pub fn upper(expr: impl Into<Expr>) -> Self {
+        Self::func("upper", vec![expr.into()])
+    }
+
+    /// Creates a `trim(expr [, characters])` call.
+    pub fn trim(args: Vec<Expr>) -> Self {
+        Self::func("trim", args)
+    }
+
+    /// Creates a `substr(expr, start [, length])` call.
+    pub fn substr(args: Vec<Expr>) -> Self {
+        Self::func("substr", args)
+    }
+
+    /// Creates a `replace(expr, pattern, replacement)` call.
+    pub fn replace(
+        expr: impl Into<Expr>,
+        pattern: impl Into<Expr>,
+        replacement: impl Into<Expr>,
+    ) -> Self {
+        Self::func(
+            "replace",
+            vec![expr.into(), pattern.into(), replacement.into()],
+        )
+    }
+
+    /// Creates an `instr(expr, search)` call.
+    pub fn instr(expr: impl Into<Expr>, search: impl Into<Expr>) -> Self {
+        Self::func("instr", vec![expr.into(), search.into()])
+    }
+
+    /// Creates an `abs(expr)` call.
+    pub fn abs(expr: impl Into<Expr>) -> Self {
+        Self::func("abs", vec![expr.into()])
+    }
+
+    /// Creates a `round(expr [, digits])` call.
+    pub fn round(args: Vec<Expr>) -> Self {
+        Self::func("round", args)
+    }
+}
+
+/// Helpers for SQLite's math functions, available when SQLite is compiled with
+/// `SQLITE_ENABLE_MATH_FUNCTIONS`, as it is by default since version 3.35.0.
+impl Expr {
+    /// Creates a `ceil(expr)` call.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Select};
+    ///
+    /// let mut select = Select::new("orders");
+    /// select
+    ///     .column_as(Expr::ceil(Expr::col("total") / 10), "bucket")
+    ///     .column_as(Expr::pow(Expr::col("rate"), 2), "squared")
+    ///     .column_as(Expr::log_base(2, Expr::col("size")), "bits");
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "SELECT ceil(total / 10) AS bucket, pow(rate, 2) AS squared, log(2, size) AS bits FROM orders;"
+    /// );
+    /// ```
+    pub fn ceil(expr: impl Into<Expr>) -> Self {
+        Self::func("ceil", vec![expr.into()])
+    }
+
+    /// Creates a `floor(expr)` call.
+    pub fn floor(expr: impl Into<Expr>) -> Self {
+        Self::func("floor", vec![expr.into()])
+    }
+
+    /// Creates a `trunc(expr)` call, which rounds toward zero.
+    pub fn trunc(expr: impl Into<Expr>) -> Self {
+        Self::func("trunc", vec![expr.into()])
+    }
+
+    /// Creates a `sign(expr)` call, which returns `-1`, `0` or `1`.
+    pub fn sign(expr: impl Into<Expr>) -> Self {
+        Self::func("sign", vec![expr.into()])
+    }
+
+    /// Creates a `sqrt(expr)` call.
+    pub fn sqrt(expr: impl Into<Expr>) -> Self {
+        Self::func("sqrt", vec![expr.into()])
+    }
+
+    /// Creates a `pow(base, exponent)` call.
+    pub fn pow(base: impl Into<Expr>, exponent: impl Into<Expr>) -> Self {
+        Self::func("pow", vec![base.into(), exponent.into()])
+    }
+
+    /// Creates an `exp(expr)` call.
+    pub fn exp(expr: impl Into<Expr>) -> Self {
+        Self::func("exp", vec![expr.into()])
+    }
+
+    /// Creates an `ln(expr)` call, the natural logarithm.
+    pub fn ln(expr: impl Into<Expr>) -> Self {
+        Self::func("ln", vec![expr.into()])
+    }
+
+    /// Creates a `log(expr)` call, the base-10 logarithm.
+    pub fn log(expr: impl Into<Expr>) -> Self {
+        Self::func("log", vec![expr.into()])
+    }
+
+    /// Creates a `log(base, expr)` call, the logarithm in the given base.
+    pub fn log_base(base: impl Into<Expr>, expr: impl Into<Expr>) -> Self {
+        Self::func("log", vec![base.into(), expr.into()])
+    }
+
+    /// Creates a `log2(expr)` call.
+    pub fn log2(expr: impl Into<Expr>) -> Self {
+        Self::func("log2", vec![expr.into()])
+    }
+
+    /// Creates a `mod(lhs, rhs)` call, the remainder of a division that, unlike `%`,
+    /// also accepts real numbers.
+    pub fn modulo(lhs: impl Into<Expr>, rhs: impl Into<Expr>) -> Self {
+        Self::func("mod", vec![lhs.into(), rhs.into()])
+    }
+
+    /// Creates a `pi()` call.
+    pub fn pi() -> Self {
+        Self::func("pi", Vec::new())
+    }
+}
+
+/// Helpers for SQLite's date and time functions, whose arguments are a time value
+/// followed by modifiers.
+impl Expr {
+    /// Creates a `date(...)` call.
+    pub fn date(args: Vec<Expr>) -> Self {
+        Self::func("date", args)
+    }
+
+    /// Creates a `time(...)` call.
+    pub fn time(args: Vec<Expr>) -> Self {
+        Self::func("time", args)
+    }
+
+    /// Creates a `datetime(...)` call.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Select};
+    ///
+    /// let mut select = Select::new("events");
+    /// select.condition(
+    ///     Expr::col("created_at").greater_than(Expr::datetime(vec![
+    ///         Expr::value("now"),
+    ///         Expr::value("-7 days"),
+    ///     ])),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "SELECT * FROM events WHERE created_at > datetime('now', '-7 days');"
+    /// );
+    /// ```
+    pub fn datetime(args: Vec<Expr>) -> Self {
+        Self::func("datetime", args)
+    }
+
+    /// Creates a `julianday(...)` call.
+    pub fn julianday(args: Vec<Expr>) -> Self {
+        Self::func("julianday", args)
+    }
+
+    /// Creates a `strftime(format, ...)` call.
+    pub fn strftime(format: &str, args: Vec<Expr>) -> Self {
+        let mut all = vec![Self::value(format)];
+        all.extend(args);
+        Self::func("strftime", all)
+    }
+}
+
+/// Helpers for SQLite's window functions, to be used with `Expr::over`.
+impl Expr {
+    /// Creates a `row_number()` call.
+    pub fn row_number() -> Self {
+        Self::func("row_number", Vec::new())
+    }
+
+    /// Creates a `rank()` call.
+    pub fn rank() -> Self {
+        Self::func("rank", Vec::new())
+    }
+
+    /// Creates a `dense_rank()` call.
+    pub fn dense_rank() -> Self {
+        Self::func("dense_rank", Vec::new())
+    }
+
+    /// Creates a `lag(expr [, offset [, default]])` call.
+    pub fn lag(args: Vec<Expr>) -> Self {
+        Self::func("lag", args)
+    }
+
+    /// Creates a `lead(expr [, offset [, default]])` call.
+    pub fn lead(args: Vec<Expr>) -> Self {
+        Self::func("lead", args)
+    }
+}
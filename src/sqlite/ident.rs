@@ -0,0 +1,406 @@
+use super::{SqlWriter, WriteSql};
+use crate::errors::SqlBuilderError;
+
+/// SQLite's keywords, which must be quoted to be used as names.
+const KEYWORDS: [&str; 147] = [
+    "ABORT",
+    "ACTION",
+    "ADD",
+    "AFTER",
+    "ALL",
+    "ALTER",
+    "ALWAYS",
+    "ANALYZE",
+    "AND",
+    "AS",
+    "ASC",
+    "ATTACH",
+    "AUTOINCREMENT",
+    "BEFORE",
+    "BEGIN",
+    "BETWEEN",
+    "BY",
+    "CASCADE",
+    "CASE",
+    "CAST",
+    "CHECK",
+    "COLLATE",
+    "COLUMN",
+    "COMMIT",
+    "CONFLICT",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "CURRENT",
+    "CURRENT_DATE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "DATABASE",
+    "DEFAULT",
+    "DEFERRABLE",
+    "DEFERRED",
+    "DELETE",
+    "DESC",
+    "DETACH",
+    "DISTINCT",
+    "DO",
+    "DROP",
+    "EACH",
+    "ELSE",
+    "END",
+    "ESCAPE",
+    "EXCEPT",
+    "EXCLUDE",
+    "EXCLUSIVE",
+    "EXISTS",
+    "EXPLAIN",
+    "FAIL",
+    "FILTER",
+    "FIRST",
+    "FOLLOWING",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GENERATED",
+    "GLOB",
+    "GROUP",
+    "GROUPS",
+    "HAVING",
+    "IF",
+    "IGNORE",
+    "IMMEDIATE",
+    "IN",
+    "INDEX",
+    "INDEXED",
+    "INITIALLY",
+    "INNER",
+    "INSERT",
+    "INSTEAD",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "ISNULL",
+    "JOIN",
+    "KEY",
+    "LAST",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "MATCH",
+    "MATERIALIZED",
+    "NATURAL",
+    "NO",
+    "NOT",
+    "NOTHING",
+    "NOTNULL",
+    "NULL",
+    "NULLS",
+    "OF",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OTHERS",
+    "OUTER",
+    "OVER",
+    "PARTITION",
+    "PLAN",
+    "PRAGMA",
+    "PRECEDING",
+    "PRIMARY",
+    "QUERY",
+    "RAISE",
+    "RANGE",
+    "RECURSIVE",
+    "REFERENCES",
+    "REGEXP",
+    "REINDEX",
+    "RELEASE",
+    "RENAME",
+    "REPLACE",
+    "RESTRICT",
+    "RETURNING",
+    "RIGHT",
+    "ROLLBACK",
+    "ROW",
+    "ROWS",
+    "SAVEPOINT",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "TEMP",
+    "TEMPORARY",
+    "THEN",
+    "TIES",
+    "TO",
+    "TRANSACTION",
+    "TRIGGER",
+    "UNBOUNDED",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USING",
+    "VACUUM",
+    "VALUES",
+    "VIEW",
+    "VIRTUAL",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+    "WITHOUT",
+];
+
+/// Returns `true` if the word is one of SQLite's keywords.
+pub(crate) fn is_keyword(word: &str) -> bool {
+    KEYWORDS
+        .binary_search(&word.to_ascii_uppercase().as_str())
+        .is_ok()
+}
+
+/// Returns `true` if the name can be written without quotes, keywords aside.
+fn is_plain(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Returns `true` if the part is a plain name or a name between well-formed quotes.
+fn is_name(part: &str) -> bool {
+    let inner = |open: char, close: char| {
+        part.len() >= 2 && part.starts_with(open) && part.ends_with(close)
+    };
+    match part.chars().next() {
+        Some('"') => inner('"', '"') && !part[1..part.len() - 1].replace("\"\"", "").contains('"'),
+        Some('`') => inner('`', '`') && !part[1..part.len() - 1].replace("``", "").contains('`'),
+        Some('[') => inner('[', ']') && !part[1..part.len() - 1].contains(']'),
+        _ => part.is_empty() || is_plain(part),
+    }
+}
+
+/// Removes the quotes around a name written as `"name"`, `` `name` `` or `[name]`.
+fn unquote(name: &str) -> String {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next_back()) {
+        (Some('"'), Some('"')) if name.len() >= 2 => chars.as_str().replace("\"\"", "\""),
+        (Some('`'), Some('`')) if name.len() >= 2 => chars.as_str().replace("``", "`"),
+        (Some('['), Some(']')) => chars.as_str().to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// Splits `text` on `separator` where it is outside of quotes.
+fn split_unquoted(text: &str, separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (None, '"' | '`') if i == start => quote = Some(c),
+            (None, '[') if i == start => quote = Some(']'),
+            (Some(q), c) if c == q => quote = None,
+            (None, c) if separator(c) => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Represents when an identifier is written between quotes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Quoting {
+    /// Quotes every part of the name.
+    Always,
+    /// Quotes the parts that are keywords or are not plain identifiers.
+    #[default]
+    WhenNeeded,
+    /// Writes the name as given, rejecting parts that are not plain identifiers.
+    Never,
+}
+
+/// Represents a possibly schema-qualified name, such as a table or a column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ident {
+    parts: Vec<String>,
+    quoting: Option<Quoting>,
+}
+
+impl Ident {
+    /// Creates an unqualified `Ident`, taking the name exactly as given.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Ident, Quoting};
+    ///
+    /// assert_eq!(Ident::new("users").build().unwrap(), "users");
+    /// assert_eq!(Ident::new("order").build().unwrap(), "\"order\"");
+    /// assert_eq!(Ident::new("first name").build().unwrap(), "\"first name\"");
+    /// assert_eq!(
+    ///     Ident::new("users").quoting(Quoting::Always).build().unwrap(),
+    ///     "\"users\""
+    /// );
+    /// assert!(Ident::new("a\"; DROP TABLE users; --").quoting(Quoting::Never).build().is_err());
+    /// ```
+    pub fn new(name: &str) -> Self {
+        Self {
+            parts: vec![name.to_string()],
+            quoting: None,
+        }
+    }
+
+    /// Creates an `Ident` qualified with a schema, such as `main.users`.
+    pub fn qualified(schema: &str, name: &str) -> Self {
+        Self {
+            parts: vec![schema.to_string(), name.to_string()],
+            quoting: None,
+        }
+    }
+
+    /// Creates an `Ident` from a dotted name such as `main.users`, where parts may be quoted.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Ident;
+    ///
+    /// let ident = Ident::parse("main.\"user.data\"");
+    /// assert_eq!(ident, Ident::qualified("main", "user.data"));
+    /// assert_eq!(ident.build().unwrap(), "main.\"user.data\"");
+    /// ```
+    pub fn parse(name: &str) -> Self {
+        Self {
+            parts: split_unquoted(name, |c| c == '.')
+                .into_iter()
+                .map(unquote)
+                .collect(),
+            quoting: None,
+        }
+    }
+
+    /// Creates an `Ident` from a dotted name like `parse`, returning `None` if a part is
+    /// neither a plain name nor quoted, as in `lower(name)`.
+    pub(crate) fn parse_strict(name: &str) -> Option<Self> {
+        split_unquoted(name, |c| c == '.')
+            .into_iter()
+            .all(is_name)
+            .then(|| Self::parse(name))
+    }
+
+    /// Creates an `Ident` from a dotted name like `parse_strict`. A name that is not one is
+    /// kept as a name that cannot be written unquoted, so it is reported when built.
+    pub(crate) fn strict(name: &str) -> Self {
+        Self::parse_strict(name).unwrap_or_else(|| Self::new(name).quoting(Quoting::Never))
+    }
+
+    /// Creates an unqualified `Ident` from a name that may be quoted.
+    pub(crate) fn unqualified(name: &str) -> Self {
+        Self::new(&unquote(name))
+    }
+
+    /// Sets when the name is written between quotes, overriding the policy of the statement.
+    pub fn quoting(mut self, quoting: Quoting) -> Self {
+        self.quoting = Some(quoting);
+        self
+    }
+
+    /// Builds and returns the SQL representation of the name.
+    pub fn build(&self) -> Result<String, SqlBuilderError> {
+        self.render()
+    }
+}
+
+impl From<&str> for Ident {
+    fn from(name: &str) -> Self {
+        Self::parse(name)
+    }
+}
+
+impl WriteSql for Ident {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        for (i, part) in self.parts.iter().enumerate() {
+            if part.is_empty() {
+                return Err(SqlBuilderError::EmptyColumnName);
+            }
+            if part.contains('\0') {
+                return Err(SqlBuilderError::InvalidIdentifier(part.clone()));
+            }
+
+            let quoted = match self.quoting.unwrap_or(w.quoting()) {
+                Quoting::Always => true,
+                Quoting::WhenNeeded => !is_plain(part) || is_keyword(part),
+                Quoting::Never if is_plain(part) => false,
+                Quoting::Never => return Err(SqlBuilderError::InvalidIdentifier(part.clone())),
+            };
+
+            if i > 0 {
+                w.push('.');
+            }
+            match quoted {
+                true => w.push_str(&format!("\"{}\"", part.replace('"', "\"\""))),
+                false => w.push_str(part),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a comma-separated list of names, each of which may be qualified.
+pub(crate) fn write_names(w: &mut SqlWriter, names: &[String]) -> Result<(), SqlBuilderError> {
+    for (i, name) in names.iter().enumerate() {
+        if i > 0 {
+            w.push_str(", ");
+        }
+        Ident::parse(name).write_sql(w)?;
+    }
+    Ok(())
+}
+
+/// Represents a table name with an optional alias, as written after `FROM` or `JOIN`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TableName {
+    name: Ident,
+    alias: Option<(&'static str, Ident)>,
+}
+
+impl TableName {
+    /// Parses a table reference written as `name`, `name alias` or `name AS alias`, removing
+    /// the quotes of each name.
+    pub(crate) fn parse(table: &str) -> Result<Self, SqlBuilderError> {
+        let words: Vec<&str> = split_unquoted(table.trim(), char::is_whitespace)
+            .into_iter()
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        let (name, alias) = match words.as_slice() {
+            [] => return Err(SqlBuilderError::EmptyTableName),
+            [name] => (name, None),
+            [name, alias] => (name, Some((" ", alias))),
+            [name, r#as, alias] if r#as.eq_ignore_ascii_case("AS") => (name, Some((" AS ", alias))),
+            _ => return Err(SqlBuilderError::InvalidIdentifier(table.to_string())),
+        };
+
+        Ok(Self {
+            name: Ident::parse(name),
+            alias: alias.map(|(separator, alias)| (separator, Ident::unqualified(alias))),
+        })
+    }
+}
+
+impl WriteSql for TableName {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        self.name.write_sql(w)?;
+        if let Some((separator, alias)) = &self.alias {
+            w.push_str(separator);
+            alias.write_sql(w)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a table reference written as `name`, `name alias` or `name AS alias`.
+pub(crate) fn write_table(w: &mut SqlWriter, table: &str) -> Result<(), SqlBuilderError> {
+    TableName::parse(table)?.write_sql(w)
+}
@@ -1,11 +1,31 @@
-use super::BuildableStatement;
+use super::{
+    write_names, write_returning, write_table, write_with, BuildableStatement, Compound,
+    ConflictResolution, OnConflict, Parser, Placeholder, Quoting, ResultColumn, SqlWriter, Value,
+    With, WriteSql,
+};
 use crate::errors::SqlBuilderError;
 
+/// Default value of SQLite's `SQLITE_MAX_VARIABLE_NUMBER` since version 3.32.0.
+pub const SQLITE_MAX_VARIABLE_NUMBER: usize = 32766;
+
+/// Default value of SQLite's `SQLITE_MAX_COMPOUND_SELECT`.
+pub const SQLITE_MAX_COMPOUND_SELECT: usize = 500;
+
 /// Represents the creation of a INSERT with specified table and values.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Insert {
+    with: Option<With>,
     pub table: String,
-    pub values: Vec<(String, String)>,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    select: Option<Compound>,
+    default_values: bool,
+    or: Option<ConflictResolution>,
+    upserts: Vec<OnConflict>,
+    returning: Vec<ResultColumn>,
+    max_variables: usize,
+    max_rows: usize,
+    quoting: Option<Quoting>,
 }
 
 impl Insert {
@@ -26,51 +46,389 @@ impl Insert {
     /// assert_eq!(insert, "INSERT INTO metas_clientes_tb (name, age, department, salary, hired_date, manager_id) VALUES ('João', '30', 'TI', '5000.00', '2024-03-20', '1');")
     pub fn new(table: &str) -> Self {
         Self {
+            with: None,
             table: table.to_string(),
-            values: Vec::new(),
+            columns: Vec::new(),
+            rows: Vec::new(),
+            select: None,
+            default_values: false,
+            or: None,
+            upserts: Vec::new(),
+            returning: Vec::new(),
+            max_variables: SQLITE_MAX_VARIABLE_NUMBER,
+            max_rows: SQLITE_MAX_COMPOUND_SELECT,
+            quoting: None,
         }
     }
 
-    /// Sets the values to be inserted.
-    pub fn values(mut self, values: Vec<(&str, &str)>) -> Self {
-        self.values = values
+    /// Creates a new `Insert` instance from a SQL statement string.
+    ///
+    /// Rows only hold values, so a `VALUES` list with other expressions, such as `(1 + 2)`,
+    /// fails with `SqlBuilderError::Unsupported`.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Insert;
+    ///
+    /// let query = "INSERT INTO users (name, age) VALUES ('João', 30);";
+    /// let insert = Insert::from(query).unwrap();
+    /// assert_eq!(insert.build().unwrap(), query);
+    /// ```
+    pub fn from(statement: &str) -> Result<Insert, SqlBuilderError> {
+        Parser::parse(statement, Parser::parse_insert)
+    }
+
+    /// Sets the values to be inserted as a single row, replacing any previous columns and rows.
+    pub fn values<T: Into<Value>>(mut self, values: Vec<(&str, T)>) -> Self {
+        let (columns, row): (Vec<_>, Vec<_>) = values
+            .into_iter()
+            .map(|(col, val)| (col.to_string(), val.into()))
+            .unzip();
+        self.columns = columns;
+        self.rows = vec![row];
+        self
+    }
+
+    /// Sets the columns that each following `row` provides values for.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Insert, Value};
+    ///
+    /// let insert = Insert::new("users")
+    ///     .columns(["name", "age"])
+    ///     .row([Value::from("João"), Value::from(30)])
+    ///     .row([Value::from("Maria"), Value::from(25)])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(insert, "INSERT INTO users (name, age) VALUES ('João', 30), ('Maria', 25);");
+    /// ```
+    pub fn columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.columns = columns
             .into_iter()
-            .map(|(col, val)| (col.to_string(), val.to_string()))
+            .map(|col| col.as_ref().to_string())
             .collect();
         self
     }
 
+    /// Adds a row of values, in the same order as `columns`. Without `columns`, the statement
+    /// has no column list and each row gives a value for every column of the table.
+    pub fn row<I, T>(mut self, row: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Value>,
+    {
+        self.rows.push(row.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Inserts the rows returned by a `Select` or `Compound` instead of a `VALUES` list.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Insert, Select};
+    ///
+    /// let mut select = Select::new("users");
+    /// select.columns("name, email").condition(Expr::raw("active = 1"));
+    ///
+    /// let insert = Insert::new("archive")
+    ///     .columns(["name", "email"])
+    ///     .select(select)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     insert,
+    ///     "INSERT INTO archive (name, email) SELECT name, email FROM users WHERE active = 1;"
+    /// );
+    /// ```
+    pub fn select(mut self, select: impl Into<Compound>) -> Self {
+        self.select = Some(select.into());
+        self.default_values = false;
+        self
+    }
+
+    /// Inserts a single row where every column takes its default value.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Insert;
+    ///
+    /// let insert = Insert::new("logs").default_values().build().unwrap();
+    /// assert_eq!(insert, "INSERT INTO logs DEFAULT VALUES;");
+    /// ```
+    pub fn default_values(mut self) -> Self {
+        self.default_values = true;
+        self.select = None;
+        self
+    }
+
+    /// Prefixes the statement with a `WITH` clause.
+    pub fn with(mut self, with: With) -> Self {
+        self.with = Some(with);
+        self
+    }
+
+    /// Sets the conflict resolution algorithm, rendered as `INSERT OR ...`.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{ConflictResolution, Insert};
+    ///
+    /// let insert = Insert::new("users")
+    ///     .or(ConflictResolution::Ignore)
+    ///     .values(vec![("name", "João")])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(insert, "INSERT OR IGNORE INTO users (name) VALUES ('João');");
+    /// ```
+    pub fn or(mut self, resolution: ConflictResolution) -> Self {
+        self.or = Some(resolution);
+        self
+    }
+
+    /// Adds an `ON CONFLICT` upsert clause. Only the last clause may omit the conflict target.
+    pub fn on_conflict(mut self, on_conflict: OnConflict) -> Self {
+        self.upserts.push(on_conflict);
+        self
+    }
+
+    /// Adds columns to the `RETURNING` clause, where a string names a column and
+    /// `ResultColumn::All` renders `*`.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Insert, ResultColumn};
+    ///
+    /// let insert = Insert::new("users")
+    ///     .values(vec![("name", "João")])
+    ///     .returning([ResultColumn::from("id"), ResultColumn::All])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(insert, "INSERT INTO users (name) VALUES ('João') RETURNING id, *;");
+    /// ```
+    pub fn returning<I, C>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<ResultColumn>,
+    {
+        self.returning.extend(columns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Overrides the limits used by `build_batches`, for SQLite builds compiled with
+    /// non-default `SQLITE_MAX_VARIABLE_NUMBER` or `SQLITE_MAX_COMPOUND_SELECT`.
+    pub fn batch_limits(mut self, max_variables: usize, max_rows: usize) -> Self {
+        self.max_variables = max_variables.max(1);
+        self.max_rows = max_rows.max(1);
+        self
+    }
+
+    /// Sets when the names of the statement are written between quotes, for names that do
+    /// not set their own policy.
+    pub fn quoting(mut self, quoting: Quoting) -> Self {
+        self.quoting = Some(quoting);
+        self
+    }
+
     /// Builds and returns the SQL statement for the `INSERT` query.
     pub fn build(&self) -> Result<String, SqlBuilderError> {
+        Ok(format!("{};", self.render()?))
+    }
+
+    /// Builds the SQL statement with placeholders and returns the values to bind.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Insert, Placeholder, Value};
+    ///
+    /// let (sql, params) = Insert::new("users")
+    ///     .values(vec![("name", "João"), ("age", "30")])
+    ///     .build_with_params(Placeholder::Anonymous)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(sql, "INSERT INTO users (name, age) VALUES (?, ?);");
+    /// assert_eq!(params, vec![Value::from("João"), Value::from("30")]);
+    /// ```
+    pub fn build_with_params(
+        &self,
+        placeholder: Placeholder,
+    ) -> Result<(String, Vec<Value>), SqlBuilderError> {
+        let (sql, params) = self.render_with_params(placeholder)?;
+        Ok((format!("{};", sql), params))
+    }
+
+    /// Builds one statement per chunk of rows, keeping each statement within
+    /// `SQLITE_MAX_VARIABLE_NUMBER` bound values and `SQLITE_MAX_COMPOUND_SELECT` rows.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Insert;
+    ///
+    /// let mut insert = Insert::new("numbers").columns(["n"]).batch_limits(999, 2);
+    /// for n in 0..5 {
+    ///     insert = insert.row([n]);
+    /// }
+    ///
+    /// assert_eq!(
+    ///     insert.build_batches().unwrap(),
+    ///     vec![
+    ///         "INSERT INTO numbers (n) VALUES (0), (1);",
+    ///         "INSERT INTO numbers (n) VALUES (2), (3);",
+    ///         "INSERT INTO numbers (n) VALUES (4);",
+    ///     ]
+    /// );
+    /// ```
+    pub fn build_batches(&self) -> Result<Vec<String>, SqlBuilderError> {
+        self.validate()?;
+        if self.rows.is_empty() {
+            return Ok(vec![self.build()?]);
+        }
+        self.batches()
+            .map(|rows| {
+                let mut w = SqlWriter::inline();
+                self.write_rows(&mut w, rows)?;
+                w.push(';');
+                Ok(w.finish().0)
+            })
+            .collect()
+    }
+
+    /// Builds one statement per chunk of rows with placeholders, as `build_batches` does,
+    /// each paired with the values to bind.
+    pub fn build_batches_with_params(
+        &self,
+        placeholder: Placeholder,
+    ) -> Result<Vec<(String, Vec<Value>)>, SqlBuilderError> {
+        self.validate()?;
+        if self.rows.is_empty() {
+            return Ok(vec![self.build_with_params(placeholder)?]);
+        }
+        self.batches()
+            .map(|rows| {
+                let mut w = SqlWriter::bound(placeholder);
+                self.write_rows(&mut w, rows)?;
+                w.push(';');
+                Ok(w.finish())
+            })
+            .collect()
+    }
+
+    /// Internal method to split the rows into chunks that respect the batch limits.
+    fn batches(&self) -> std::slice::Chunks<'_, Vec<Value>> {
+        let per_row = self.width().max(1);
+        let size = (self.max_variables / per_row).clamp(1, self.max_rows);
+        self.rows.chunks(size)
+    }
+
+    /// Internal method to get the number of values in each row: one per column, or the
+    /// length of the first row when the statement has no column list.
+    fn width(&self) -> usize {
+        match (self.columns.len(), self.rows.first()) {
+            (0, Some(row)) => row.len(),
+            (len, _) => len,
+        }
+    }
+
+    /// Internal method to check the table, columns and every row before rendering.
+    fn validate(&self) -> Result<(), SqlBuilderError> {
         if self.table.is_empty() {
             return Err(SqlBuilderError::EmptyTableName);
         }
 
-        if self.values.is_empty() {
+        if self.columns.iter().any(|col| col.is_empty()) {
+            return Err(SqlBuilderError::EmptyColumnName);
+        }
+
+        if self.default_values {
+            if !self.columns.is_empty() || !self.rows.is_empty() || !self.upserts.is_empty() {
+                return Err(SqlBuilderError::InvalidQuery);
+            }
+        } else if let Some(select) = &self.select {
+            // SQLite cannot tell an upsert's `ON` from a join constraint unless the SELECT has a WHERE.
+            if !self.rows.is_empty() || (!self.upserts.is_empty() && !select.ends_unambiguously()) {
+                return Err(SqlBuilderError::InvalidQuery);
+            }
+        } else if self.rows.is_empty() {
             return Err(SqlBuilderError::EmptyColumnAndValue);
         }
 
-        let mut columns: Vec<String> = vec![];
-        let mut values: Vec<String> = vec![];
+        let targeted = self.upserts.len().saturating_sub(1);
+        if self.upserts[..targeted].iter().any(|u| !u.has_target()) {
+            return Err(SqlBuilderError::InvalidQuery);
+        }
 
-        for (col, val) in &self.values {
-            if col.is_empty() {
-                return Err(SqlBuilderError::EmptyColumnName);
+        let width = self.width();
+        for row in &self.rows {
+            if row.is_empty() {
+                return Err(SqlBuilderError::EmptyColumnAndValue);
             }
-            if val.is_empty() {
+            if row.len() != width {
+                return Err(SqlBuilderError::ColumnCountMismatch {
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            if row.iter().any(Value::is_empty) {
                 return Err(SqlBuilderError::EmptyValue);
             }
+        }
+
+        Ok(())
+    }
+
+    /// Internal method to write the statement for the given rows under its quoting policy.
+    fn write_rows(&self, w: &mut SqlWriter, rows: &[Vec<Value>]) -> Result<(), SqlBuilderError> {
+        w.with_quoting(self.quoting, |w| self.write_statement(w, rows))
+    }
+
+    /// Internal method to write the statement for the given rows, or for its `SELECT` or `DEFAULT VALUES` source.
+    fn write_statement(
+        &self,
+        w: &mut SqlWriter,
+        rows: &[Vec<Value>],
+    ) -> Result<(), SqlBuilderError> {
+        write_with(w, &self.with)?;
+        w.push_str("INSERT ");
+        if let Some(resolution) = &self.or {
+            w.push_str(&format!("OR {} ", resolution.build()));
+        }
+        w.push_str("INTO ");
+        write_table(w, &self.table)?;
+        if !self.columns.is_empty() {
+            w.push_str(" (");
+            write_names(w, &self.columns)?;
+            w.push(')');
+        }
 
-            columns.push(col.clone());
-            values.push(format!("'{}'", val.clone()));
+        if self.default_values {
+            w.push_str(" DEFAULT VALUES");
+        } else if let Some(select) = &self.select {
+            w.push(' ');
+            select.write_sql(w)?;
+        }
+        for (i, row) in rows.iter().enumerate() {
+            w.push_str(if i == 0 { " VALUES " } else { ", " });
+            w.push('(');
+            for (j, val) in row.iter().enumerate() {
+                if j > 0 {
+                    w.push_str(", ");
+                }
+                w.push_value(val);
+            }
+            w.push(')');
         }
+        for upsert in &self.upserts {
+            w.push(' ');
+            upsert.write_sql(w)?;
+        }
+        write_returning(w, &self.returning)
+    }
+}
 
-        Ok(format!(
-            "INSERT INTO {} ({}) VALUES ({});",
-            self.table,
-            columns.join(", "),
-            values.join(", ")
-        ))
+impl WriteSql for Insert {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        self.validate()?;
+        self.write_rows(w, &self.rows)
     }
 }
 
@@ -1,8 +1,8 @@
-use super::BuildableStatement;
+use super::{BuildableStatement, Compound, Expr, IntoCondition, SqlWriter, TableRef, WriteSql};
 use crate::errors::SqlBuilderError;
 
 /// Represents the possible join types.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum JoinType {
     Inner,
     Left,
@@ -10,6 +10,8 @@ pub enum JoinType {
     RightOuter,
     LeftOuter,
     Full,
+    FullOuter,
+    Cross,
 }
 
 /// Implementation of the `BuildableStatement` trait for `JoinType`, allowing it to be printed.
@@ -22,48 +24,114 @@ impl BuildableStatement for JoinType {
             Self::RightOuter => "RIGHT OUTER",
             Self::LeftOuter => "LEFT OUTER",
             Self::Full => "FULL",
+            Self::FullOuter => "FULL OUTER",
+            Self::Cross => "CROSS",
         })
     }
 }
 
 /// Represents a ´JOIN´ clause builder for SQL queries
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Join {
-    table: String,
+    table: TableRef,
     join_type: JoinType,
-    on: String,
+    on: Option<Expr>,
 }
 
 impl Join {
-    /// Creates a new `Select` instance with the specified table name.
+    /// Creates a new `Join` on the specified table. An empty `on`, such as `Where::new()`, leaves out
+    /// the `ON` clause of a `CROSS JOIN`.
     /// # Example
     /// ```
-    /// use lumus_sql_builder::sqlite::{Join, JoinType};
-    /// let join = Join::new("phones p", JoinType::Inner, "p.user_id = u.user_id").build().unwrap();
+    /// use lumus_sql_builder::sqlite::{Expr, Join, JoinType};
+    /// let on = Expr::col("p.user_id").equal_to(Expr::col("u.user_id"));
+    /// let join = Join::new("phones p", JoinType::Inner, on).build().unwrap();
     /// assert_eq!(join, "INNER JOIN phones p ON p.user_id = u.user_id");
     /// ```
-    pub fn new(table: &str, join_type: JoinType, on: &str) -> Self {
+    pub fn new(table: &str, join_type: JoinType, on: impl IntoCondition) -> Self {
         Self {
-            table: table.to_string(),
+            table: TableRef::named(table),
             join_type,
-            on: on.to_string(),
+            on: on_clause(on.into_condition()),
         }
     }
 
-    pub fn build(self) -> Result<String, SqlBuilderError> {
-        if self.table.is_empty() {
-            return Err(SqlBuilderError::EmptyTableName);
+    /// Creates a new `Join` on a subquery used as a derived table under the given alias.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Join, JoinType, Select};
+    ///
+    /// let mut totals = Select::new("orders");
+    /// totals.columns("user_id, sum(total) AS spent").group("user_id");
+    ///
+    /// let on = Expr::col("t.user_id").equal_to(Expr::col("u.id"));
+    /// let join = Join::subquery(totals, "t", JoinType::Left, on).build().unwrap();
+    /// assert_eq!(
+    ///     join,
+    ///     "LEFT JOIN (SELECT user_id, sum(total) AS spent FROM orders GROUP BY user_id) t ON t.user_id = u.id"
+    /// );
+    /// ```
+    pub fn subquery(
+        query: impl Into<Compound>,
+        alias: &str,
+        join_type: JoinType,
+        on: impl IntoCondition,
+    ) -> Self {
+        Self {
+            table: TableRef::Subquery(Box::new(query.into()), alias.to_string()),
+            join_type,
+            on: on_clause(on.into_condition()),
         }
+    }
+
+    pub fn build(self) -> Result<String, SqlBuilderError> {
+        self.render()
+    }
+}
 
-        if self.on.is_empty() {
+/// Drops an empty `ON` condition, such as an empty `Where` or empty raw SQL.
+fn on_clause(on: Expr) -> Option<Expr> {
+    match on {
+        Expr::Value(value) if value.is_empty() => None,
+        on if on.is_empty() => None,
+        on => Some(on),
+    }
+}
+
+impl WriteSql for Join {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        if self.on.is_none() && !matches!(self.join_type, JoinType::Cross) {
+            if matches!(&self.table, TableRef::Invalid(table) if table.is_empty()) {
+                return Err(SqlBuilderError::EmptyTableName);
+            }
             return Err(SqlBuilderError::EmptyOnClause);
         }
 
-        Ok(format!(
-            "{} JOIN {} ON {}",
-            self.join_type.build(),
-            self.table,
-            self.on
-        ))
+        w.push_str(&format!("{} JOIN ", self.join_type.build()));
+        self.table.write_sql(w)?;
+        if let Some(on) = &self.on {
+            w.push_str(" ON ");
+            on.write_sql(w)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents a join of a statement, either already built or kept as a `Join` builder.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JoinClause {
+    Built(String),
+    Join(Join),
+}
+
+impl WriteSql for JoinClause {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        match self {
+            Self::Built(join) => {
+                w.push_str(join);
+                Ok(())
+            }
+            Self::Join(join) => join.write_sql(w),
+        }
     }
 }
@@ -0,0 +1,140 @@
+use super::{BuildableStatement, Expr, Ident, IntoColumnExpr, SqlWriter, WriteSql};
+use crate::errors::SqlBuilderError;
+
+/// Represents the direction of an ordering term.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+/// Implementation of the `BuildableStatement` trait for `Direction`, allowing it to be printed.
+impl BuildableStatement for Direction {
+    fn build(&self) -> String {
+        String::from(match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        })
+    }
+}
+
+/// Represents where `NULL` values are placed by an ordering term.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Nulls {
+    First,
+    Last,
+}
+
+/// Implementation of the `BuildableStatement` trait for `Nulls`, allowing it to be printed.
+impl BuildableStatement for Nulls {
+    fn build(&self) -> String {
+        String::from(match self {
+            Self::First => "NULLS FIRST",
+            Self::Last => "NULLS LAST",
+        })
+    }
+}
+
+/// Represents a term of an `ORDER BY` clause.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderTerm {
+    expr: Expr,
+    collation: Option<String>,
+    direction: Option<Direction>,
+    nulls: Option<Nulls>,
+}
+
+impl OrderTerm {
+    /// Creates a new `OrderTerm` over the given expression, where a string names a column, in
+    /// the default direction.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, OrderTerm, Select};
+    ///
+    /// let mut select = Select::new("users");
+    /// select
+    ///     .order_by([OrderTerm::new(Expr::col("name")).collate("NOCASE")])
+    ///     .order_by([Expr::col("age").desc().nulls_last()]);
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "SELECT * FROM users ORDER BY name COLLATE NOCASE, age DESC NULLS LAST;"
+    /// );
+    /// ```
+    pub fn new(expr: impl IntoColumnExpr) -> Self {
+        Self {
+            expr: expr.into_column_expr(),
+            collation: None,
+            direction: None,
+            nulls: None,
+        }
+    }
+
+    /// Sorts in ascending order.
+    pub fn asc(mut self) -> Self {
+        self.direction = Some(Direction::Asc);
+        self
+    }
+
+    /// Sorts in descending order.
+    pub fn desc(mut self) -> Self {
+        self.direction = Some(Direction::Desc);
+        self
+    }
+
+    /// Places `NULL` values before the others.
+    pub fn nulls_first(mut self) -> Self {
+        self.nulls = Some(Nulls::First);
+        self
+    }
+
+    /// Places `NULL` values after the others.
+    pub fn nulls_last(mut self) -> Self {
+        self.nulls = Some(Nulls::Last);
+        self
+    }
+
+    /// Compares values with the given collating sequence, such as `NOCASE`.
+    pub fn collate(mut self, collation: &str) -> Self {
+        self.collation = Some(collation.to_string());
+        self
+    }
+}
+
+impl<T: IntoColumnExpr> From<T> for OrderTerm {
+    fn from(expr: T) -> Self {
+        Self::new(expr)
+    }
+}
+
+impl WriteSql for OrderTerm {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        if self.expr.is_empty() {
+            return Err(SqlBuilderError::EmptyColumnName);
+        }
+        self.expr.write_sql(w)?;
+
+        if let Some(collation) = &self.collation {
+            w.push_str(" COLLATE ");
+            Ident::unqualified(collation).write_sql(w)?;
+        }
+
+        if let Some(direction) = &self.direction {
+            w.push_str(&format!(" {}", direction.build()));
+        }
+
+        if let Some(nulls) = &self.nulls {
+            w.push_str(&format!(" {}", nulls.build()));
+        }
+        Ok(())
+    }
+}
+
+/// Writes an ` ORDER BY` clause for the given terms, or nothing when there are none.
+pub(crate) fn write_order(w: &mut SqlWriter, order: &[OrderTerm]) -> Result<(), SqlBuilderError> {
+    for (i, term) in order.iter().enumerate() {
+        w.push_str(if i == 0 { " ORDER BY " } else { ", " });
+        term.write_sql(w)?;
+    }
+    Ok(())
+}
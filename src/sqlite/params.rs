@@ -0,0 +1,109 @@
+use super::{BuildableStatement, Quoting, Value};
+use crate::errors::SqlBuilderError;
+
+/// Represents the placeholder style used when values are bound as parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placeholder {
+    /// Anonymous parameters, rendered as `?`.
+    Anonymous,
+    /// Numbered parameters, rendered as `?1`, `?2`, ...
+    Numbered,
+}
+
+/// Accumulates the SQL text of a statement and, when binding, its parameter values.
+pub(crate) struct SqlWriter {
+    sql: String,
+    placeholder: Option<Placeholder>,
+    params: Vec<Value>,
+    quoting: Quoting,
+}
+
+impl SqlWriter {
+    /// Creates a writer that renders values as SQL literals.
+    pub(crate) fn inline() -> Self {
+        Self {
+            sql: String::new(),
+            placeholder: None,
+            params: Vec::new(),
+            quoting: Quoting::default(),
+        }
+    }
+
+    /// Creates a writer that renders values as placeholders and collects them in order.
+    pub(crate) fn bound(placeholder: Placeholder) -> Self {
+        Self {
+            sql: String::new(),
+            placeholder: Some(placeholder),
+            params: Vec::new(),
+            quoting: Quoting::default(),
+        }
+    }
+
+    /// Returns the quoting policy applied to names that do not set their own.
+    pub(crate) fn quoting(&self) -> Quoting {
+        self.quoting
+    }
+
+    /// Writes a statement under the given quoting policy, restoring the enclosing one after.
+    pub(crate) fn with_quoting(
+        &mut self,
+        quoting: Option<Quoting>,
+        write: impl FnOnce(&mut Self) -> Result<(), SqlBuilderError>,
+    ) -> Result<(), SqlBuilderError> {
+        let outer = self.quoting;
+        self.quoting = quoting.unwrap_or(outer);
+        let result = write(self);
+        self.quoting = outer;
+        result
+    }
+
+    pub(crate) fn push(&mut self, c: char) {
+        self.sql.push(c);
+    }
+
+    pub(crate) fn push_str(&mut self, s: &str) {
+        self.sql.push_str(s);
+    }
+
+    /// Writes a value either as a literal or as the next parameter placeholder.
+    pub(crate) fn push_value(&mut self, value: &Value) {
+        match self.placeholder {
+            None => self.sql.push_str(&value.build()),
+            Some(Placeholder::Anonymous) => {
+                self.params.push(value.clone());
+                self.sql.push('?');
+            }
+            Some(Placeholder::Numbered) => {
+                self.params.push(value.clone());
+                self.sql.push_str(&format!("?{}", self.params.len()));
+            }
+        }
+    }
+
+    /// Returns the SQL text and the collected parameter values.
+    pub(crate) fn finish(self) -> (String, Vec<Value>) {
+        (self.sql, self.params)
+    }
+}
+
+/// Implemented by every builder that can write itself into a `SqlWriter`.
+pub(crate) trait WriteSql {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError>;
+
+    /// Renders the statement with values inlined as literals.
+    fn render(&self) -> Result<String, SqlBuilderError> {
+        let mut w = SqlWriter::inline();
+        self.write_sql(&mut w)?;
+        Ok(w.finish().0)
+    }
+
+    /// Renders the statement with placeholders and returns the values to bind.
+    fn render_with_params(
+        &self,
+        placeholder: Placeholder,
+    ) -> Result<(String, Vec<Value>), SqlBuilderError> {
+        let mut w = SqlWriter::bound(placeholder);
+        self.write_sql(&mut w)?;
+        Ok(w.finish())
+    }
+}
@@ -0,0 +1,1521 @@
+use super::tokenizer::{tokenize, Token, TokenKind};
+use super::{
+    is_keyword, BinaryOp, Case, Collation, Column, ColumnOption, Compound, CompoundOperator,
+    ConflictResolution, CreateTable, Cte, Delete, Direction, Expr, ForeignKeyAction, Frame,
+    FrameBound, FrameExclude, FrameUnits, GeneratedStorage, Insert, Join, JoinType, OnConflict,
+    OrderTerm, References, ResultColumn, Select, TableConstraint, UnaryOp, Update, Value, Window,
+    With,
+};
+use crate::errors::SqlBuilderError;
+
+/// Keywords that end an expression and therefore cannot be used as bare names or implicit aliases.
+const RESERVED: [&str; 52] = [
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "CAST",
+    "COLLATE",
+    "CROSS",
+    "CURRENT_DATE",
+    "CURRENT_TIME",
+    "CURRENT_TIMESTAMP",
+    "DESC",
+    "DISTINCT",
+    "ELSE",
+    "END",
+    "ESCAPE",
+    "EXCEPT",
+    "EXISTS",
+    "FROM",
+    "FULL",
+    "GLOB",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INNER",
+    "INTERSECT",
+    "IS",
+    "ISNULL",
+    "JOIN",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NATURAL",
+    "NOT",
+    "NOTNULL",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "RETURNING",
+    "RIGHT",
+    "SELECT",
+    "THEN",
+    "UNION",
+    "USING",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+];
+
+fn is_reserved(word: &str) -> bool {
+    RESERVED.iter().any(|r| r.eq_ignore_ascii_case(word))
+}
+
+/// Renders an identifier read from a quoted token back into its quoted form.
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Reads back the text of a `'a' || char(0) || 'b'` concatenation.
+fn nul_text(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Value(Value::Text(text)) => Some(text.clone()),
+        Expr::Function { name, args, .. }
+            if name.eq_ignore_ascii_case("char") && args == &[Expr::value(0)] =>
+        {
+            Some(String::from('\0'))
+        }
+        Expr::Binary(lhs, BinaryOp::Concat, rhs) => Some(nul_text(lhs)? + &nul_text(rhs)?),
+        _ => None,
+    }
+}
+
+/// A recursive-descent parser over the tokens of a single SQL statement.
+pub(crate) struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    /// Tokenizes `sql`, runs `parse` over it and checks that nothing but a `;` is left.
+    pub(crate) fn parse<T>(
+        sql: &str,
+        parse: impl FnOnce(&mut Parser) -> Result<T, SqlBuilderError>,
+    ) -> Result<T, SqlBuilderError> {
+        let mut parser = Self {
+            tokens: tokenize(sql)?,
+            pos: 0,
+        };
+        let result = parse(&mut parser)?;
+        parser.eat_symbol(";");
+        if parser.peek() != &TokenKind::Eof {
+            return Err(parser.error("end of statement"));
+        }
+        Ok(result)
+    }
+
+    pub(crate) fn peek(&self) -> &TokenKind {
+        self.peek_nth(0)
+    }
+
+    pub(crate) fn peek_nth(&self, n: usize) -> &TokenKind {
+        let last = self.tokens.len() - 1;
+        &self.tokens[(self.pos + n).min(last)].kind
+    }
+
+    fn advance(&mut self) -> TokenKind {
+        let kind = self.peek().clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        kind
+    }
+
+    /// Builds an error describing what was expected at the current token.
+    pub(crate) fn error(&self, expected: &str) -> SqlBuilderError {
+        let token = &self.tokens[self.pos];
+        SqlBuilderError::UnexpectedToken {
+            position: token.position,
+            expected: expected.to_string(),
+            found: token.kind.describe(),
+        }
+    }
+
+    /// Returns an error for SQL that is valid but cannot be represented by the builders,
+    /// located at the current token.
+    pub(crate) fn unsupported(&self, feature: &str) -> SqlBuilderError {
+        SqlBuilderError::Unsupported {
+            position: self.tokens[self.pos].position,
+            feature: feature.to_string(),
+        }
+    }
+
+    pub(crate) fn is_keyword_at(&self, n: usize, keyword: &str) -> bool {
+        matches!(self.peek_nth(n), TokenKind::Word(w) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    pub(crate) fn is_keyword(&self, keyword: &str) -> bool {
+        self.is_keyword_at(0, keyword)
+    }
+
+    pub(crate) fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.is_keyword(keyword) {
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    pub(crate) fn expect_keyword(&mut self, keyword: &str) -> Result<(), SqlBuilderError> {
+        if self.eat_keyword(keyword) {
+            return Ok(());
+        }
+        Err(self.error(&format!("'{}'", keyword)))
+    }
+
+    pub(crate) fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), TokenKind::Symbol(s) if *s == symbol)
+    }
+
+    pub(crate) fn eat_symbol(&mut self, symbol: &str) -> bool {
+        if self.is_symbol(symbol) {
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    pub(crate) fn expect_symbol(&mut self, symbol: &str) -> Result<(), SqlBuilderError> {
+        if self.eat_symbol(symbol) {
+            return Ok(());
+        }
+        Err(self.error(&format!("'{}'", symbol)))
+    }
+
+    /// Returns `true` if the current token can start a name.
+    fn is_name(&self) -> bool {
+        match self.peek() {
+            TokenKind::Word(w) => !is_reserved(w),
+            TokenKind::QuotedIdent(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Parses an identifier, keeping quoted identifiers quoted.
+    pub(crate) fn parse_name(&mut self) -> Result<String, SqlBuilderError> {
+        if !self.is_name() {
+            return Err(self.error("identifier"));
+        }
+        match self.advance() {
+            TokenKind::QuotedIdent(i) => Ok(quote_ident(&i)),
+            TokenKind::Word(w) => Ok(w),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Parses an identifier, removing the quotes of a quoted identifier.
+    fn parse_unquoted_name(&mut self) -> Result<String, SqlBuilderError> {
+        if !self.is_name() {
+            return Err(self.error("identifier"));
+        }
+        match self.advance() {
+            TokenKind::QuotedIdent(i) | TokenKind::Word(i) => Ok(i),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Parses a dotted name such as `main.users`.
+    pub(crate) fn parse_qualified_name(&mut self) -> Result<String, SqlBuilderError> {
+        let mut name = self.parse_name()?;
+        while self.is_symbol(".") && !matches!(self.peek_nth(1), TokenKind::Symbol("*")) {
+            self.advance();
+            name.push('.');
+            name.push_str(&self.parse_name()?);
+        }
+        Ok(name)
+    }
+
+    /// Parses an optional `[AS] alias`.
+    fn parse_alias(&mut self) -> Result<Option<String>, SqlBuilderError> {
+        if self.eat_keyword("AS") || self.is_name() {
+            return Ok(Some(self.parse_name()?));
+        }
+        Ok(None)
+    }
+
+    /// Parses a non-negative integer literal.
+    pub(crate) fn parse_u32(&mut self) -> Result<u32, SqlBuilderError> {
+        if let TokenKind::Number(n) = self.peek() {
+            if let Ok(n) = n.parse::<u32>() {
+                self.advance();
+                return Ok(n);
+            }
+        }
+        Err(self.error("non-negative integer"))
+    }
+
+    /// Parses a comma-separated list using `item`.
+    pub(crate) fn parse_list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Parser) -> Result<T, SqlBuilderError>,
+    ) -> Result<Vec<T>, SqlBuilderError> {
+        let mut items = vec![item(self)?];
+        while self.eat_symbol(",") {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Parses a `SELECT` statement without the trailing semicolon.
+    pub(crate) fn parse_select(&mut self) -> Result<Select, SqlBuilderError> {
+        let with = self.parse_with()?;
+        self.expect_keyword("SELECT")?;
+
+        let distinct = self.eat_keyword("DISTINCT");
+        if !distinct {
+            self.eat_keyword("ALL");
+        }
+
+        let columns = self.parse_list(Self::parse_result_column)?;
+
+        self.expect_keyword("FROM")?;
+        let mut select = match self.parse_derived_table()? {
+            Some((query, alias)) => Select::from_subquery(query, &alias),
+            None => Select::new(&self.parse_table()?),
+        };
+
+        if let Some(with) = with {
+            select.with(with);
+        }
+        if distinct {
+            select.distinct();
+        }
+        if columns != [ResultColumn::All] {
+            select.columns(columns);
+        }
+
+        while let Some(join) = self.parse_join()? {
+            select.join_clause(join);
+        }
+        if self.is_symbol(",") {
+            return Err(self.unsupported("Comma joins"));
+        }
+
+        if self.eat_keyword("WHERE") {
+            select.condition(self.parse_expr()?);
+        }
+
+        if self.eat_keyword("GROUP") {
+            self.expect_keyword("BY")?;
+            select.group_by(self.parse_list(Self::parse_expr)?);
+        }
+
+        if self.eat_keyword("HAVING") {
+            select.having(self.parse_expr()?);
+        }
+
+        if self.eat_keyword("WINDOW") {
+            for (name, window) in self.parse_list(Self::parse_named_window)? {
+                select.window(&name, window);
+            }
+        }
+
+        select.order_by(self.parse_order()?);
+
+        let (limit, offset) = self.parse_limit()?;
+        if let Some(limit) = limit {
+            select.limit(limit);
+        }
+        if let Some(offset) = offset {
+            select.offset(offset);
+        }
+
+        Ok(select)
+    }
+
+    /// Parses a compound `SELECT`, a single `SELECT` being a compound with one branch.
+    pub(crate) fn parse_compound(&mut self) -> Result<Compound, SqlBuilderError> {
+        let mut compound = Compound::new(self.parse_select()?);
+        loop {
+            let position = self.pos;
+            let Some(operator) = self.parse_compound_operator() else {
+                break;
+            };
+            // Only the last branch may end with ORDER BY or LIMIT, which apply to the compound.
+            if compound.last_branch().has_order_or_limit() {
+                self.pos = position;
+                return Err(self.error("end of statement"));
+            }
+            compound.branch(operator, self.parse_select()?);
+        }
+        compound.take_trailing_clauses();
+        Ok(compound)
+    }
+
+    /// Parses the set operator between two branches of a compound `SELECT`.
+    fn parse_compound_operator(&mut self) -> Option<CompoundOperator> {
+        if self.eat_keyword("UNION") {
+            match self.eat_keyword("ALL") {
+                true => Some(CompoundOperator::UnionAll),
+                false => Some(CompoundOperator::Union),
+            }
+        } else if self.eat_keyword("INTERSECT") {
+            Some(CompoundOperator::Intersect)
+        } else if self.eat_keyword("EXCEPT") {
+            Some(CompoundOperator::Except)
+        } else {
+            None
+        }
+    }
+
+    /// Parses an optional `ORDER BY` clause, returning no terms when it is absent.
+    fn parse_order(&mut self) -> Result<Vec<OrderTerm>, SqlBuilderError> {
+        if !self.eat_keyword("ORDER") {
+            return Ok(Vec::new());
+        }
+        self.expect_keyword("BY")?;
+        self.parse_list(Self::parse_ordering_term)
+    }
+
+    /// Parses optional `LIMIT` and `OFFSET` clauses, including the `LIMIT offset, count` form.
+    fn parse_limit(&mut self) -> Result<(Option<u32>, Option<u32>), SqlBuilderError> {
+        if self.eat_keyword("LIMIT") {
+            let limit = self.parse_u32()?;
+            if self.eat_keyword("OFFSET") {
+                Ok((Some(limit), Some(self.parse_u32()?)))
+            } else if self.eat_symbol(",") {
+                Ok((Some(self.parse_u32()?), Some(limit)))
+            } else {
+                Ok((Some(limit), None))
+            }
+        } else if self.eat_keyword("OFFSET") {
+            Ok((None, Some(self.parse_u32()?)))
+        } else {
+            Ok((None, None))
+        }
+    }
+
+    /// Parses an optional `WITH` clause and its common table expressions.
+    fn parse_with(&mut self) -> Result<Option<With>, SqlBuilderError> {
+        if !self.eat_keyword("WITH") {
+            return Ok(None);
+        }
+
+        let mut with = With::new();
+        if self.eat_keyword("RECURSIVE") {
+            with = with.recursive();
+        }
+
+        let ctes = self.parse_list(|p| {
+            let name = p.parse_name()?;
+            let mut columns = Vec::new();
+            if p.eat_symbol("(") {
+                columns = p.parse_list(Self::parse_name)?;
+                p.expect_symbol(")")?;
+            }
+
+            p.expect_keyword("AS")?;
+            let materialized = if p.eat_keyword("MATERIALIZED") {
+                Some(true)
+            } else if p.eat_keyword("NOT") {
+                p.expect_keyword("MATERIALIZED")?;
+                Some(false)
+            } else {
+                None
+            };
+
+            p.expect_symbol("(")?;
+            let cte = Cte::new(&name, p.parse_compound()?).columns(columns);
+            p.expect_symbol(")")?;
+            Ok(match materialized {
+                Some(true) => cte.materialized(),
+                Some(false) => cte.not_materialized(),
+                None => cte,
+            })
+        })?;
+
+        Ok(Some(ctes.into_iter().fold(with, With::cte)))
+    }
+
+    /// Parses a result column: `*`, `table.*` or an expression with an optional alias.
+    pub(crate) fn parse_result_column(&mut self) -> Result<ResultColumn, SqlBuilderError> {
+        if self.eat_symbol("*") {
+            return Ok(ResultColumn::All);
+        }
+
+        if self.is_name()
+            && matches!(self.peek_nth(1), TokenKind::Symbol("."))
+            && matches!(self.peek_nth(2), TokenKind::Symbol("*"))
+        {
+            let table = self.parse_unquoted_name()?;
+            self.advance();
+            self.advance();
+            // `all_from` reads the table as a dotted name, so only a name it would misread stays quoted.
+            return Ok(match table.contains(['.', '"', '`', '[']) {
+                true => ResultColumn::AllFrom(quote_ident(&table)),
+                false => ResultColumn::AllFrom(table),
+            });
+        }
+
+        let expr = self.parse_expr()?;
+        let alias = match self.eat_keyword("AS") || self.is_name() {
+            true => Some(self.parse_unquoted_name()?),
+            false => None,
+        };
+        Ok(ResultColumn::Expr(expr, alias))
+    }
+
+    /// Parses a table name with an optional alias, keeping the `AS` of the alias.
+    fn parse_table(&mut self) -> Result<String, SqlBuilderError> {
+        let table = self.parse_qualified_name()?;
+        let separator = match self.eat_keyword("AS") {
+            true => " AS ",
+            false if self.is_name() => " ",
+            false => return Ok(table),
+        };
+        Ok(format!("{}{}{}", table, separator, self.parse_name()?))
+    }
+
+    /// Parses a parenthesized subquery and its required alias, returning `None` if the next
+    /// token does not start one.
+    fn parse_derived_table(&mut self) -> Result<Option<(Compound, String)>, SqlBuilderError> {
+        if !self.eat_symbol("(") {
+            return Ok(None);
+        }
+        let query = self.parse_compound()?;
+        self.expect_symbol(")")?;
+        match self.parse_alias()? {
+            Some(alias) => Ok(Some((query, alias))),
+            None => Err(self.error("alias")),
+        }
+    }
+
+    /// Parses a join clause, returning `None` if the next token does not start one.
+    fn parse_join(&mut self) -> Result<Option<Join>, SqlBuilderError> {
+        if self.is_keyword("NATURAL") {
+            return Err(self.unsupported("NATURAL joins"));
+        }
+        let join_type = if self.eat_keyword("CROSS") {
+            JoinType::Cross
+        } else if self.eat_keyword("INNER") || self.is_keyword("JOIN") {
+            JoinType::Inner
+        } else if self.eat_keyword("LEFT") {
+            match self.eat_keyword("OUTER") {
+                true => JoinType::LeftOuter,
+                false => JoinType::Left,
+            }
+        } else if self.eat_keyword("RIGHT") {
+            match self.eat_keyword("OUTER") {
+                true => JoinType::RightOuter,
+                false => JoinType::Right,
+            }
+        } else if self.eat_keyword("FULL") {
+            match self.eat_keyword("OUTER") {
+                true => JoinType::FullOuter,
+                false => JoinType::Full,
+            }
+        } else {
+            return Ok(None);
+        };
+
+        self.expect_keyword("JOIN")?;
+        let derived = self.parse_derived_table()?;
+        let table = match derived {
+            Some(_) => String::new(),
+            None => self.parse_table()?,
+        };
+
+        let on = if self.eat_keyword("ON") {
+            self.parse_expr()?
+        } else if self.is_keyword("USING") {
+            return Err(self.unsupported("Joins with USING"));
+        } else if matches!(join_type, JoinType::Cross) {
+            Expr::raw("")
+        } else {
+            return Err(self.error("'ON'"));
+        };
+
+        Ok(Some(match derived {
+            Some((query, alias)) => Join::subquery(query, &alias, join_type, on),
+            None => Join::new(&table, join_type, on),
+        }))
+    }
+
+    /// Parses a `name AS (...)` item of a `WINDOW` clause.
+    fn parse_named_window(&mut self) -> Result<(String, Window), SqlBuilderError> {
+        let name = self.parse_name()?;
+        self.expect_keyword("AS")?;
+        Ok((name, self.parse_window()?))
+    }
+
+    /// Parses a parenthesized window definition.
+    fn parse_window(&mut self) -> Result<Window, SqlBuilderError> {
+        self.expect_symbol("(")?;
+        let mut window = Window::new();
+
+        let clause = ["PARTITION", "ORDER", "ROWS", "RANGE", "GROUPS"];
+        if self.is_name() && !clause.iter().any(|keyword| self.is_keyword(keyword)) {
+            window = window.base(&self.parse_name()?);
+        }
+
+        if self.eat_keyword("PARTITION") {
+            self.expect_keyword("BY")?;
+            window = window.partition_by(self.parse_list(Self::parse_expr)?);
+        }
+
+        window = window.order_by(self.parse_order()?);
+
+        let units = if self.eat_keyword("ROWS") {
+            Some(FrameUnits::Rows)
+        } else if self.eat_keyword("RANGE") {
+            Some(FrameUnits::Range)
+        } else if self.eat_keyword("GROUPS") {
+            Some(FrameUnits::Groups)
+        } else {
+            None
+        };
+        if let Some(units) = units {
+            window = window.frame(self.parse_frame(units)?);
+        }
+
+        self.expect_symbol(")")?;
+        Ok(window)
+    }
+
+    /// Parses the bounds and exclusion of a window frame after its units.
+    fn parse_frame(&mut self, units: FrameUnits) -> Result<Frame, SqlBuilderError> {
+        let mut frame = match self.eat_keyword("BETWEEN") {
+            true => {
+                let start = self.parse_frame_bound()?;
+                self.expect_keyword("AND")?;
+                Frame::between(units, start, self.parse_frame_bound()?)
+            }
+            false => Frame::new(units, self.parse_frame_bound()?),
+        };
+
+        if self.eat_keyword("EXCLUDE") {
+            let exclude = if self.eat_keyword("NO") {
+                self.expect_keyword("OTHERS")?;
+                FrameExclude::NoOthers
+            } else if self.eat_keyword("CURRENT") {
+                self.expect_keyword("ROW")?;
+                FrameExclude::CurrentRow
+            } else if self.eat_keyword("GROUP") {
+                FrameExclude::Group
+            } else {
+                self.expect_keyword("TIES")?;
+                FrameExclude::Ties
+            };
+            frame = frame.exclude(exclude);
+        }
+        Ok(frame)
+    }
+
+    /// Parses a frame bound such as `UNBOUNDED PRECEDING` or `3 FOLLOWING`.
+    fn parse_frame_bound(&mut self) -> Result<FrameBound, SqlBuilderError> {
+        if self.eat_keyword("UNBOUNDED") {
+            return match self.eat_keyword("PRECEDING") {
+                true => Ok(FrameBound::UnboundedPreceding),
+                false => {
+                    self.expect_keyword("FOLLOWING")?;
+                    Ok(FrameBound::UnboundedFollowing)
+                }
+            };
+        }
+        if self.eat_keyword("CURRENT") {
+            self.expect_keyword("ROW")?;
+            return Ok(FrameBound::CurrentRow);
+        }
+
+        let offset = self.parse_expr()?;
+        match self.eat_keyword("PRECEDING") {
+            true => Ok(FrameBound::Preceding(offset)),
+            false => {
+                self.expect_keyword("FOLLOWING")?;
+                Ok(FrameBound::Following(offset))
+            }
+        }
+    }
+
+    /// Parses an `ORDER BY` term with its optional collation, direction and null ordering.
+    fn parse_ordering_term(&mut self) -> Result<OrderTerm, SqlBuilderError> {
+        let mut term = OrderTerm::new(self.parse_or()?);
+        if self.eat_keyword("COLLATE") {
+            term = term.collate(&self.parse_name()?);
+        }
+        if self.eat_keyword("ASC") {
+            term = term.asc();
+        } else if self.eat_keyword("DESC") {
+            term = term.desc();
+        }
+        if self.eat_keyword("NULLS") {
+            if self.eat_keyword("FIRST") {
+                term = term.nulls_first();
+            } else {
+                self.expect_keyword("LAST")?;
+                term = term.nulls_last();
+            }
+        }
+        Ok(term)
+    }
+
+    /// Parses a literal value, such as the items of a `VALUES` list, including text
+    /// with NUL characters written as `('a' || char(0) || 'b')` by `escape_value`.
+    /// Other expressions are reported as unsupported, since rows only hold values.
+    fn parse_value(&mut self) -> Result<Value, SqlBuilderError> {
+        let position = self.pos;
+        let expr = self.parse_expr()?;
+        let value = match expr {
+            Expr::Value(value) => Some(value),
+            Expr::Nested(expr) => nul_text(&expr).map(Value::Text),
+            _ => None,
+        };
+        value.ok_or_else(|| {
+            self.pos = position;
+            self.unsupported("Expressions in VALUES")
+        })
+    }
+
+    /// Parses an `INSERT` statement without the trailing semicolon.
+    pub(crate) fn parse_insert(&mut self) -> Result<Insert, SqlBuilderError> {
+        let with = self.parse_with()?;
+        let or = if self.eat_keyword("REPLACE") {
+            Some(ConflictResolution::Replace)
+        } else {
+            self.expect_keyword("INSERT")?;
+            match self.eat_keyword("OR") {
+                true => Some(self.parse_conflict_resolution()?),
+                false => None,
+            }
+        };
+        self.expect_keyword("INTO")?;
+        let table = self.parse_qualified_name()?;
+
+        let mut columns = Vec::new();
+        if self.eat_symbol("(") {
+            columns = self.parse_list(Self::parse_name)?;
+            self.expect_symbol(")")?;
+        }
+
+        let mut insert = Insert::new(&table).columns(&columns);
+        if columns.is_empty() && self.eat_keyword("DEFAULT") {
+            self.expect_keyword("VALUES")?;
+            insert = insert.default_values();
+        } else if self.is_keyword("SELECT") {
+            insert = insert.select(self.parse_compound()?);
+        } else {
+            self.expect_keyword("VALUES")?;
+            let mut width = columns.len();
+            let rows = self.parse_list(|p| {
+                p.expect_symbol("(")?;
+                let row = p.parse_list(Self::parse_value)?;
+                if width == 0 {
+                    width = row.len();
+                }
+                if row.len() != width {
+                    return Err(p.error(&format!("{} values", width)));
+                }
+                p.expect_symbol(")")?;
+                Ok(row)
+            })?;
+            insert = rows.into_iter().fold(insert, Insert::row);
+        }
+
+        if let Some(with) = with {
+            insert = insert.with(with);
+        }
+        if let Some(or) = or {
+            insert = insert.or(or);
+        }
+        while self.is_keyword("ON") {
+            insert = insert.on_conflict(self.parse_on_conflict()?);
+        }
+        Ok(insert.returning(self.parse_returning()?))
+    }
+
+    /// Parses the algorithm that follows `INSERT OR`.
+    fn parse_conflict_resolution(&mut self) -> Result<ConflictResolution, SqlBuilderError> {
+        let resolution = match self.peek() {
+            TokenKind::Word(w) => match w.to_ascii_uppercase().as_str() {
+                "ROLLBACK" => ConflictResolution::Rollback,
+                "ABORT" => ConflictResolution::Abort,
+                "FAIL" => ConflictResolution::Fail,
+                "IGNORE" => ConflictResolution::Ignore,
+                "REPLACE" => ConflictResolution::Replace,
+                _ => return Err(self.error("conflict resolution")),
+            },
+            _ => return Err(self.error("conflict resolution")),
+        };
+        self.advance();
+        Ok(resolution)
+    }
+
+    /// Parses an `ON CONFLICT` upsert clause.
+    fn parse_on_conflict(&mut self) -> Result<OnConflict, SqlBuilderError> {
+        self.expect_keyword("ON")?;
+        self.expect_keyword("CONFLICT")?;
+
+        let mut on_conflict = OnConflict::new();
+        if self.eat_symbol("(") {
+            on_conflict = on_conflict.target(self.parse_list(Self::parse_name)?);
+            self.expect_symbol(")")?;
+            if self.eat_keyword("WHERE") {
+                on_conflict = on_conflict.target_condition(self.parse_expr()?);
+            }
+        }
+
+        self.expect_keyword("DO")?;
+        if self.eat_keyword("NOTHING") {
+            return Ok(on_conflict.do_nothing());
+        }
+
+        self.expect_keyword("UPDATE")?;
+        self.expect_keyword("SET")?;
+        let set = self.parse_list(|p| {
+            let column = p.parse_name()?;
+            p.expect_symbol("=")?;
+            Ok((column, p.parse_expr()?))
+        })?;
+        on_conflict = set
+            .into_iter()
+            .fold(on_conflict, |on_conflict, (column, expr)| {
+                on_conflict.do_update_expr(&column, expr)
+            });
+        if self.eat_keyword("WHERE") {
+            on_conflict = on_conflict.update_condition(self.parse_expr()?);
+        }
+        Ok(on_conflict)
+    }
+
+    /// Parses an `UPDATE` statement without the trailing semicolon.
+    pub(crate) fn parse_update(&mut self) -> Result<Update, SqlBuilderError> {
+        let with = self.parse_with()?;
+        self.expect_keyword("UPDATE")?;
+        let table = self.parse_qualified_name()?;
+
+        self.expect_keyword("SET")?;
+        let set = self.parse_list(|p| {
+            let column = p.parse_name()?;
+            p.expect_symbol("=")?;
+            Ok((column, p.parse_expr()?))
+        })?;
+        let mut update = set
+            .into_iter()
+            .fold(Update::new(&table), |update, (column, expr)| {
+                update.set_expr(&column, expr)
+            });
+
+        if let Some(with) = with {
+            update.with(with);
+        }
+        if self.eat_keyword("FROM") {
+            update.from_table(&self.parse_table()?);
+            while let Some(join) = self.parse_join()? {
+                update.join_clause(join);
+            }
+            if self.is_symbol(",") {
+                return Err(self.unsupported("Comma joins"));
+            }
+        }
+        if self.eat_keyword("WHERE") {
+            update.condition(self.parse_expr()?);
+        }
+        update.returning(self.parse_returning()?);
+
+        update.order_by(self.parse_order()?);
+        let (limit, offset) = self.parse_limit()?;
+        if let Some(limit) = limit {
+            update.limit(limit);
+        }
+        if let Some(offset) = offset {
+            update.offset(offset);
+        }
+        Ok(update)
+    }
+
+    /// Parses a `DELETE` statement without the trailing semicolon.
+    pub(crate) fn parse_delete(&mut self) -> Result<Delete, SqlBuilderError> {
+        let with = self.parse_with()?;
+        self.expect_keyword("DELETE")?;
+        self.expect_keyword("FROM")?;
+        let mut delete = Delete::new(&self.parse_qualified_name()?);
+        if let Some(with) = with {
+            delete.with(with);
+        }
+        if self.eat_keyword("WHERE") {
+            delete.condition(self.parse_expr()?);
+        }
+        delete.returning(self.parse_returning()?);
+
+        delete.order_by(self.parse_order()?);
+        let (limit, offset) = self.parse_limit()?;
+        if let Some(limit) = limit {
+            delete.limit(limit);
+        }
+        if let Some(offset) = offset {
+            delete.offset(offset);
+        }
+        Ok(delete)
+    }
+
+    /// Parses an optional `RETURNING` clause, returning no columns when it is absent.
+    fn parse_returning(&mut self) -> Result<Vec<ResultColumn>, SqlBuilderError> {
+        if !self.eat_keyword("RETURNING") {
+            return Ok(Vec::new());
+        }
+        self.parse_list(Self::parse_result_column)
+    }
+
+    /// Parses a `CREATE TABLE` statement without the trailing semicolon.
+    pub(crate) fn parse_create_table(&mut self) -> Result<CreateTable, SqlBuilderError> {
+        self.expect_keyword("CREATE")?;
+        let temporary = self.eat_keyword("TEMP") || self.eat_keyword("TEMPORARY");
+        self.expect_keyword("TABLE")?;
+
+        let if_not_exists = self.eat_keyword("IF");
+        if if_not_exists {
+            self.expect_keyword("NOT")?;
+            self.expect_keyword("EXISTS")?;
+        }
+
+        let table = self.parse_qualified_name()?;
+        self.expect_symbol("(")?;
+        let mut columns = vec![self.parse_column()?];
+        let mut constraints = Vec::new();
+        while self.eat_symbol(",") {
+            match constraints.is_empty() && !self.is_table_constraint() {
+                true => columns.push(self.parse_column()?),
+                false => constraints.push(self.parse_table_constraint()?),
+            }
+        }
+        self.expect_symbol(")")?;
+
+        let mut create_table = constraints
+            .into_iter()
+            .fold(CreateTable::new(&table, columns), CreateTable::constraint);
+        if self.is_keyword("STRICT") || self.is_keyword("WITHOUT") {
+            loop {
+                create_table = if self.eat_keyword("STRICT") {
+                    create_table.strict()
+                } else {
+                    self.expect_keyword("WITHOUT")?;
+                    self.expect_keyword("ROWID")?;
+                    create_table.without_rowid()
+                };
+                if !self.eat_symbol(",") {
+                    break;
+                }
+            }
+        }
+
+        if temporary {
+            create_table = create_table.temporary();
+        }
+        Ok(match if_not_exists {
+            true => create_table.if_not_exists(),
+            false => create_table,
+        })
+    }
+
+    /// Returns `true` if the next token starts a table constraint.
+    fn is_table_constraint(&self) -> bool {
+        ["CONSTRAINT", "PRIMARY", "UNIQUE", "CHECK", "FOREIGN"]
+            .iter()
+            .any(|keyword| self.is_keyword(keyword))
+    }
+
+    /// Parses a table constraint inside `CREATE TABLE`.
+    fn parse_table_constraint(&mut self) -> Result<TableConstraint, SqlBuilderError> {
+        let name = match self.eat_keyword("CONSTRAINT") {
+            true => Some(self.parse_name()?),
+            false => None,
+        };
+
+        let constraint = if self.eat_keyword("PRIMARY") {
+            self.expect_keyword("KEY")?;
+            TableConstraint::primary_key(self.parse_column_names()?)
+        } else if self.eat_keyword("UNIQUE") {
+            TableConstraint::unique(self.parse_column_names()?)
+        } else if self.eat_keyword("CHECK") {
+            self.expect_symbol("(")?;
+            let condition = self.parse_expr()?;
+            self.expect_symbol(")")?;
+            TableConstraint::check(condition)
+        } else if self.eat_keyword("FOREIGN") {
+            self.expect_keyword("KEY")?;
+            let columns = self.parse_column_names()?;
+            self.expect_keyword("REFERENCES")?;
+            TableConstraint::foreign_key(columns, self.parse_references()?)
+        } else {
+            return Err(self.error("table constraint"));
+        };
+
+        Ok(match name {
+            Some(name) => constraint.name(&name),
+            None => constraint,
+        })
+    }
+
+    /// Parses the clause of a foreign key that follows `REFERENCES`.
+    fn parse_references(&mut self) -> Result<References, SqlBuilderError> {
+        let table = self.parse_name()?;
+        let mut references = match self.is_symbol("(") {
+            true => References::new(&table, self.parse_column_names()?),
+            false => References::new(&table, Vec::<String>::new()),
+        };
+
+        loop {
+            references = if self.eat_keyword("ON") {
+                if self.eat_keyword("DELETE") {
+                    references.on_delete(self.parse_foreign_key_action()?)
+                } else {
+                    self.expect_keyword("UPDATE")?;
+                    references.on_update(self.parse_foreign_key_action()?)
+                }
+            } else if self.eat_keyword("DEFERRABLE") {
+                self.expect_keyword("INITIALLY")?;
+                self.expect_keyword("DEFERRED")?;
+                references.deferred()
+            } else {
+                return Ok(references);
+            };
+        }
+    }
+
+    /// Parses the action of an `ON DELETE` or `ON UPDATE` clause.
+    fn parse_foreign_key_action(&mut self) -> Result<ForeignKeyAction, SqlBuilderError> {
+        if self.eat_keyword("CASCADE") {
+            Ok(ForeignKeyAction::Cascade)
+        } else if self.eat_keyword("RESTRICT") {
+            Ok(ForeignKeyAction::Restrict)
+        } else if self.eat_keyword("SET") {
+            match self.eat_keyword("NULL") {
+                true => Ok(ForeignKeyAction::SetNull),
+                false => {
+                    self.expect_keyword("DEFAULT")?;
+                    Ok(ForeignKeyAction::SetDefault)
+                }
+            }
+        } else if self.eat_keyword("NO") {
+            self.expect_keyword("ACTION")?;
+            Ok(ForeignKeyAction::NoAction)
+        } else {
+            Err(self.error("foreign key action"))
+        }
+    }
+
+    /// Parses a parenthesized list of column names.
+    fn parse_column_names(&mut self) -> Result<Vec<String>, SqlBuilderError> {
+        self.expect_symbol("(")?;
+        let names = self.parse_list(Self::parse_name)?;
+        self.expect_symbol(")")?;
+        Ok(names)
+    }
+
+    /// Parses a column definition inside `CREATE TABLE`.
+    fn parse_column(&mut self) -> Result<Column, SqlBuilderError> {
+        let column = Column::new(&self.parse_name()?);
+
+        let type_name = match self.peek() {
+            TokenKind::Word(w) if !is_reserved(w) && !is_keyword(w) => self.parse_column_type()?,
+            _ => String::new(),
+        };
+        let mut column = match type_name.as_str() {
+            "" => column.untyped(),
+            "INT" => column.int(),
+            "INTEGER" => column.integer(),
+            "TEXT" => column.text(),
+            "REAL" => column.real(),
+            "BOOLEAN" => column.boolean(),
+            "BLOB" => column.blob(),
+            "NUMERIC" => column.numeric(),
+            "DATE" => column.date(),
+            "TIME" => column.time(),
+            "DATETIME" => column.datetime(),
+            "ANY" => column.any(),
+            _ => column.custom_type(&type_name),
+        };
+
+        loop {
+            column = if self.eat_keyword("NOT") {
+                self.expect_keyword("NULL")?;
+                column.not_null()
+            } else if self.eat_keyword("UNIQUE") {
+                column.unique()
+            } else if self.eat_keyword("PRIMARY") {
+                self.expect_keyword("KEY")?;
+                if self.eat_keyword("ASC") {
+                    column.primary_key_ordered(Direction::Asc)
+                } else if self.eat_keyword("DESC") {
+                    column.primary_key_ordered(Direction::Desc)
+                } else {
+                    column.primary_key()
+                }
+            } else if self.eat_keyword("ON") {
+                self.expect_keyword("CONFLICT")?;
+                column.on_conflict(self.parse_conflict_resolution()?)
+            } else if self.eat_keyword("AUTOINCREMENT") {
+                column.auto_increment()
+            } else if self.eat_keyword("CHECK") {
+                self.expect_symbol("(")?;
+                let condition = self.parse_expr()?;
+                self.expect_symbol(")")?;
+                column.check(condition)
+            } else if self.eat_keyword("GENERATED") || self.is_keyword("AS") {
+                if !self.eat_keyword("AS") {
+                    self.expect_keyword("ALWAYS")?;
+                    self.expect_keyword("AS")?;
+                }
+                self.expect_symbol("(")?;
+                let expr = self.parse_expr()?;
+                self.expect_symbol(")")?;
+                let storage = match self.eat_keyword("STORED") {
+                    true => GeneratedStorage::Stored,
+                    false => {
+                        self.eat_keyword("VIRTUAL");
+                        GeneratedStorage::Virtual
+                    }
+                };
+                column.generated(expr, storage)
+            } else if self.eat_keyword("COLLATE") {
+                column.collate(Collation::from_name(&self.parse_name()?))
+            } else if self.eat_keyword("REFERENCES") {
+                column.option(ColumnOption::References(self.parse_references()?))
+            } else if self.eat_keyword("DEFAULT") {
+                if self.eat_symbol("(") {
+                    let expr = self.parse_expr()?.build()?;
+                    self.expect_symbol(")")?;
+                    column.default_expr(&format!("({})", expr))
+                } else {
+                    match self.parse_unary()? {
+                        Expr::Value(value) => column.default(value),
+                        expr => column.default_expr(&expr.build()?),
+                    }
+                }
+            } else {
+                return Ok(column);
+            };
+        }
+    }
+
+    /// Parses an expression, reporting the `COLLATE` and `ESCAPE` operators it is followed by
+    /// as unsupported, since expressions cannot hold them.
+    pub(crate) fn parse_expr(&mut self) -> Result<Expr, SqlBuilderError> {
+        let expr = self.parse_or()?;
+        if self.is_keyword("COLLATE") {
+            return Err(self.unsupported("COLLATE operators in expressions"));
+        }
+        if self.is_keyword("ESCAPE") {
+            return Err(self.unsupported("LIKE patterns with ESCAPE"));
+        }
+        Ok(expr)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, SqlBuilderError> {
+        let mut expr = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            expr = expr.or(self.parse_and()?);
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, SqlBuilderError> {
+        let mut expr = self.parse_not()?;
+        while self.eat_keyword("AND") {
+            expr = expr.and(self.parse_not()?);
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, SqlBuilderError> {
+        if self.is_keyword("NOT") && !self.is_keyword_at(1, "EXISTS") {
+            self.advance();
+            return Ok(!self.parse_not()?);
+        }
+        self.parse_equality()
+    }
+
+    fn parse_equality(&mut self) -> Result<Expr, SqlBuilderError> {
+        let mut expr = self.parse_relational()?;
+        loop {
+            let negated = self.is_keyword("NOT")
+                && ["NULL", "LIKE", "GLOB", "IN", "BETWEEN"]
+                    .iter()
+                    .any(|k| self.is_keyword_at(1, k));
+            if negated {
+                self.advance();
+            }
+
+            expr = if self.eat_symbol("=") || self.eat_symbol("==") {
+                expr.equal_to(self.parse_relational()?)
+            } else if self.eat_symbol("!=") || self.eat_symbol("<>") {
+                expr.not_equal_to(self.parse_relational()?)
+            } else if self.eat_keyword("IS") {
+                let not = self.eat_keyword("NOT");
+                match (self.eat_keyword("NULL"), not) {
+                    (true, false) => expr.is_null(),
+                    (true, true) => expr.is_not_null(),
+                    (false, false) => expr.is(self.parse_relational()?),
+                    (false, true) => expr.is_not(self.parse_relational()?),
+                }
+            } else if self.eat_keyword("ISNULL") || (negated && self.eat_keyword("NULL")) {
+                match negated {
+                    true => expr.is_not_null(),
+                    false => expr.is_null(),
+                }
+            } else if self.eat_keyword("NOTNULL") {
+                expr.is_not_null()
+            } else if self.eat_keyword("LIKE") {
+                let pattern = self.parse_relational()?;
+                match negated {
+                    true => expr.not_like(pattern),
+                    false => expr.like(pattern),
+                }
+            } else if self.eat_keyword("GLOB") {
+                let pattern = self.parse_relational()?;
+                match negated {
+                    true => expr.not_glob(pattern),
+                    false => expr.glob(pattern),
+                }
+            } else if self.eat_keyword("IN") {
+                self.parse_in(expr, negated)?
+            } else if self.eat_keyword("BETWEEN") {
+                let low = self.parse_relational()?;
+                self.expect_keyword("AND")?;
+                let high = self.parse_relational()?;
+                match negated {
+                    true => expr.not_between(low, high),
+                    false => expr.between(low, high),
+                }
+            } else {
+                return Ok(expr);
+            };
+        }
+    }
+
+    /// Parses the parenthesized right-hand side of `[NOT] IN`.
+    fn parse_in(&mut self, expr: Expr, negated: bool) -> Result<Expr, SqlBuilderError> {
+        self.expect_symbol("(")?;
+        if self.is_keyword("SELECT") || self.is_keyword("WITH") {
+            let query = self.parse_compound()?;
+            self.expect_symbol(")")?;
+            return Ok(match negated {
+                true => expr.not_in_subquery(query),
+                false => expr.in_subquery(query),
+            });
+        }
+        let list = if self.is_symbol(")") {
+            Vec::new()
+        } else {
+            self.parse_list(Self::parse_expr)?
+        };
+        self.expect_symbol(")")?;
+        Ok(match negated {
+            true => expr.not_inside(list),
+            false => expr.inside(list),
+        })
+    }
+
+    fn parse_binary_level(
+        &mut self,
+        operators: &[(&str, BinaryOp)],
+        operand: fn(&mut Parser) -> Result<Expr, SqlBuilderError>,
+    ) -> Result<Expr, SqlBuilderError> {
+        let mut expr = operand(self)?;
+        'outer: loop {
+            for (symbol, op) in operators {
+                if self.eat_symbol(symbol) {
+                    expr = expr.binary(*op, operand(self)?);
+                    continue 'outer;
+                }
+            }
+            return Ok(expr);
+        }
+    }
+
+    fn parse_relational(&mut self) -> Result<Expr, SqlBuilderError> {
+        self.parse_binary_level(
+            &[
+                ("<=", BinaryOp::LessThanEqual),
+                (">=", BinaryOp::GreaterThanEqual),
+                ("<", BinaryOp::LessThan),
+                (">", BinaryOp::GreaterThan),
+            ],
+            Self::parse_bitwise,
+        )
+    }
+
+    fn parse_bitwise(&mut self) -> Result<Expr, SqlBuilderError> {
+        self.parse_binary_level(
+            &[
+                ("&", BinaryOp::BitAnd),
+                ("|", BinaryOp::BitOr),
+                ("<<", BinaryOp::ShiftLeft),
+                (">>", BinaryOp::ShiftRight),
+            ],
+            Self::parse_additive,
+        )
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, SqlBuilderError> {
+        self.parse_binary_level(
+            &[("+", BinaryOp::Add), ("-", BinaryOp::Subtract)],
+            Self::parse_multiplicative,
+        )
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, SqlBuilderError> {
+        self.parse_binary_level(
+            &[
+                ("*", BinaryOp::Multiply),
+                ("/", BinaryOp::Divide),
+                ("%", BinaryOp::Modulo),
+            ],
+            Self::parse_concat,
+        )
+    }
+
+    fn parse_concat(&mut self) -> Result<Expr, SqlBuilderError> {
+        self.parse_binary_level(&[("||", BinaryOp::Concat)], Self::parse_unary)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, SqlBuilderError> {
+        if self.eat_symbol("-") {
+            if let TokenKind::Number(n) = self.peek().clone() {
+                let expr = self.number(&format!("-{}", n))?;
+                self.advance();
+                return Ok(expr);
+            }
+            return Ok(-self.parse_unary()?);
+        }
+        if self.eat_symbol("+") {
+            return self.parse_unary();
+        }
+        if self.eat_symbol("~") {
+            return Ok(Expr::unary(UnaryOp::BitNot, self.parse_unary()?));
+        }
+        self.parse_primary()
+    }
+
+    /// Converts a numeric literal into an integer or real value.
+    fn number(&self, text: &str) -> Result<Expr, SqlBuilderError> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+
+        if let Some(hex) = digits.strip_prefix("0x").or(digits.strip_prefix("0X")) {
+            // Hexadecimal literals are 64-bit two's complement, as in SQLite.
+            return u64::from_str_radix(hex, 16)
+                .ok()
+                .map(|n| n as i64)
+                .and_then(|n| if negative { n.checked_neg() } else { Some(n) })
+                .map(Expr::value)
+                .ok_or_else(|| self.error("hexadecimal integer"));
+        }
+
+        if !digits.contains(['.', 'e', 'E']) {
+            if let Ok(n) = text.parse::<i64>() {
+                return Ok(Expr::value(n));
+            }
+        }
+
+        text.parse::<f64>()
+            .map(Expr::value)
+            .map_err(|_| self.error("number"))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, SqlBuilderError> {
+        match self.peek().clone() {
+            TokenKind::Number(n) => {
+                let expr = self.number(&n)?;
+                self.advance();
+                Ok(expr)
+            }
+            TokenKind::String(s) => {
+                self.advance();
+                Ok(Expr::value(s))
+            }
+            TokenKind::Blob(b) => {
+                self.advance();
+                Ok(Expr::value(b))
+            }
+            TokenKind::Variable(v) => {
+                self.advance();
+                Ok(Expr::Raw(v))
+            }
+            TokenKind::Symbol("?") => {
+                self.advance();
+                Ok(Expr::raw("?"))
+            }
+            TokenKind::Symbol("(") => {
+                self.advance();
+                let expr = if self.is_keyword("SELECT") || self.is_keyword("WITH") {
+                    Expr::subquery(self.parse_compound()?)
+                } else {
+                    self.parse_expr()?.nested()
+                };
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            TokenKind::Word(w) => self.parse_word(&w),
+            TokenKind::QuotedIdent(_) => self.parse_column_or_function(),
+            _ => Err(self.error("expression")),
+        }
+    }
+
+    /// Parses an expression that starts with a bare word.
+    fn parse_word(&mut self, word: &str) -> Result<Expr, SqlBuilderError> {
+        match word.to_uppercase().as_str() {
+            "NULL" => {
+                self.advance();
+                Ok(Expr::Value(Value::Null))
+            }
+            "TRUE" | "FALSE" => {
+                self.advance();
+                Ok(Expr::value(word.eq_ignore_ascii_case("TRUE")))
+            }
+            "CURRENT_DATE" | "CURRENT_TIME" | "CURRENT_TIMESTAMP" => {
+                self.advance();
+                Ok(Expr::Raw(word.to_uppercase()))
+            }
+            "EXISTS" | "NOT" => self.parse_exists(),
+            "CASE" => self.parse_case(),
+            "CAST" => self.parse_cast(),
+            _ => self.parse_column_or_function(),
+        }
+    }
+
+    /// Parses an `EXISTS (SELECT ...)` or `NOT EXISTS (SELECT ...)` test.
+    fn parse_exists(&mut self) -> Result<Expr, SqlBuilderError> {
+        let negated = self.eat_keyword("NOT");
+        self.expect_keyword("EXISTS")?;
+        self.expect_symbol("(")?;
+        let query = self.parse_compound()?;
+        self.expect_symbol(")")?;
+        Ok(match negated {
+            true => Expr::not_exists(query),
+            false => Expr::exists(query),
+        })
+    }
+
+    fn parse_column_or_function(&mut self) -> Result<Expr, SqlBuilderError> {
+        if matches!(self.peek_nth(1), TokenKind::Symbol("(")) {
+            let name = self.parse_name()?;
+            self.advance();
+
+            let distinct = self.eat_keyword("DISTINCT");
+            let args = if self.eat_symbol("*") {
+                vec![Expr::raw("*")]
+            } else if self.is_symbol(")") {
+                Vec::new()
+            } else {
+                self.parse_list(Self::parse_expr)?
+            };
+            self.expect_symbol(")")?;
+
+            let function = match distinct {
+                true => Expr::func_distinct(&name, args),
+                false => Expr::func(&name, args),
+            };
+            if !self.eat_keyword("OVER") {
+                return Ok(function);
+            }
+            return Ok(match self.is_symbol("(") {
+                true => function.over(self.parse_window()?),
+                false => function.over_named(&self.parse_name()?),
+            });
+        }
+
+        if !self.is_name() {
+            return Err(self.error("expression"));
+        }
+        Ok(Expr::col(&self.parse_qualified_name()?))
+    }
+
+    fn parse_case(&mut self) -> Result<Expr, SqlBuilderError> {
+        self.expect_keyword("CASE")?;
+        let mut case = match self.is_keyword("WHEN") {
+            true => Case::new(),
+            false => Case::on(self.parse_expr()?),
+        };
+
+        self.expect_keyword("WHEN")?;
+        loop {
+            let condition = self.parse_expr()?;
+            self.expect_keyword("THEN")?;
+            case = case.when(condition, self.parse_expr()?);
+            if !self.eat_keyword("WHEN") {
+                break;
+            }
+        }
+
+        if self.eat_keyword("ELSE") {
+            case = case.otherwise(self.parse_expr()?);
+        }
+        self.expect_keyword("END")?;
+        Ok(case.into())
+    }
+
+    fn parse_cast(&mut self) -> Result<Expr, SqlBuilderError> {
+        self.expect_keyword("CAST")?;
+        self.expect_symbol("(")?;
+        let expr = self.parse_expr()?;
+        self.expect_keyword("AS")?;
+        let type_name = self.parse_type_name()?;
+        self.expect_symbol(")")?;
+        Ok(expr.cast(&type_name))
+    }
+
+    /// Parses a type name such as `INTEGER`, `VARCHAR(255)` or `DECIMAL(10, 2)`.
+    pub(crate) fn parse_type_name(&mut self) -> Result<String, SqlBuilderError> {
+        let mut words = Vec::new();
+        while let TokenKind::Word(w) = self.peek() {
+            if is_reserved(w) {
+                break;
+            }
+            words.push(w.to_uppercase());
+            self.advance();
+        }
+        if words.is_empty() {
+            return Err(self.error("type name"));
+        }
+
+        let mut type_name = words.join(" ");
+        type_name.push_str(&self.parse_type_sizes()?);
+        Ok(type_name)
+    }
+
+    /// Parses the declared type of a column, one or more names such as `INT`, `VARCHAR(255)`
+    /// or `DOUBLE PRECISION`, stopping at the keyword of a column constraint.
+    pub(crate) fn parse_column_type(&mut self) -> Result<String, SqlBuilderError> {
+        let mut words = Vec::new();
+        while let TokenKind::Word(w) = self.peek() {
+            if is_reserved(w) || is_keyword(w) {
+                break;
+            }
+            words.push(w.to_uppercase());
+            self.advance();
+        }
+        if words.is_empty() {
+            return Err(self.error("column type"));
+        }
+
+        let mut type_name = words.join(" ");
+        type_name.push_str(&self.parse_type_sizes()?);
+        Ok(type_name)
+    }
+
+    /// Parses the optional sizes following a type name, as in `(10, 2)`.
+    fn parse_type_sizes(&mut self) -> Result<String, SqlBuilderError> {
+        if !self.eat_symbol("(") {
+            return Ok(String::new());
+        }
+        let sizes = self.parse_list(|p| match p.peek().clone() {
+            TokenKind::Number(n) => {
+                p.advance();
+                Ok(n)
+            }
+            _ => Err(p.error("number")),
+        })?;
+        self.expect_symbol(")")?;
+        Ok(format!("({})", sizes.join(", ")))
+    }
+}
@@ -0,0 +1,134 @@
+use super::{Expr, Ident, IntoColumnExpr, Parser, SqlWriter, WriteSql};
+use crate::errors::SqlBuilderError;
+
+/// Represents an item of a result column list, such as the columns of a `RETURNING` clause.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultColumn {
+    /// `*`
+    All,
+    /// `table.*`
+    AllFrom(String),
+    /// An expression with an optional alias.
+    Expr(Expr, Option<String>),
+}
+
+impl ResultColumn {
+    /// Creates a result column that renders an expression as the given alias.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Delete, Expr, ResultColumn};
+    ///
+    /// let mut delete = Delete::new("users");
+    /// delete.returning([
+    ///     ResultColumn::from(Expr::col("id")),
+    ///     ResultColumn::aliased(Expr::col("name"), "removed"),
+    /// ]);
+    ///
+    /// assert_eq!(delete.build().unwrap(), "DELETE FROM users RETURNING id, name AS removed;");
+    /// ```
+    pub fn aliased(expr: impl IntoColumnExpr, alias: &str) -> Self {
+        Self::Expr(expr.into_column_expr(), Some(alias.to_string()))
+    }
+
+    /// Creates a result column that selects every column of the given table, as `table.*`.
+    pub fn all_from(table: &str) -> Self {
+        Self::AllFrom(table.to_string())
+    }
+
+    /// Returns `true` if the column calls an aggregate function.
+    pub(crate) fn has_aggregate(&self) -> bool {
+        match self {
+            Self::All | Self::AllFrom(_) => false,
+            Self::Expr(Expr::Raw(sql), None) => {
+                Parser::parse(sql, |p| p.parse_list(Parser::parse_result_column))
+                    .is_ok_and(|columns| columns.iter().any(Self::has_aggregate))
+            }
+            Self::Expr(expr, _) => expr.has_aggregate(),
+        }
+    }
+}
+
+impl<T: IntoColumnExpr> From<T> for ResultColumn {
+    fn from(expr: T) -> Self {
+        Self::Expr(expr.into_column_expr(), None)
+    }
+}
+
+/// Converts a value into the items of a result column list.
+///
+/// A `&str` is kept as a single piece of raw SQL, such as `"name, age"`, while arrays and
+/// vectors add one column per item, where a string item names a column.
+pub trait IntoResultColumns {
+    fn into_result_columns(self) -> Vec<ResultColumn>;
+}
+
+impl IntoResultColumns for &str {
+    fn into_result_columns(self) -> Vec<ResultColumn> {
+        vec![ResultColumn::from(Expr::raw(self))]
+    }
+}
+
+impl IntoResultColumns for &String {
+    fn into_result_columns(self) -> Vec<ResultColumn> {
+        self.as_str().into_result_columns()
+    }
+}
+
+impl IntoResultColumns for String {
+    fn into_result_columns(self) -> Vec<ResultColumn> {
+        vec![ResultColumn::from(Expr::raw(&self))]
+    }
+}
+
+impl<T: Into<ResultColumn>, const N: usize> IntoResultColumns for [T; N] {
+    fn into_result_columns(self) -> Vec<ResultColumn> {
+        self.into_iter().map(Into::into).collect()
+    }
+}
+
+impl<T: Into<ResultColumn>> IntoResultColumns for Vec<T> {
+    fn into_result_columns(self) -> Vec<ResultColumn> {
+        self.into_iter().map(Into::into).collect()
+    }
+}
+
+impl WriteSql for ResultColumn {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        match self {
+            Self::All => w.push('*'),
+            Self::AllFrom(table) => {
+                if table.is_empty() {
+                    return Err(SqlBuilderError::EmptyTableName);
+                }
+                Ident::parse(table).write_sql(w)?;
+                w.push_str(".*");
+            }
+            Self::Expr(expr, alias) => {
+                if expr.is_empty() {
+                    return Err(SqlBuilderError::EmptyColumnName);
+                }
+                expr.write_sql(w)?;
+                if let Some(alias) = alias {
+                    if alias.is_empty() {
+                        return Err(SqlBuilderError::EmptyColumnName);
+                    }
+                    w.push_str(" AS ");
+                    Ident::unqualified(alias).write_sql(w)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a ` RETURNING` clause for the given columns, or nothing when there are none.
+pub(crate) fn write_returning(
+    w: &mut SqlWriter,
+    columns: &[ResultColumn],
+) -> Result<(), SqlBuilderError> {
+    for (i, column) in columns.iter().enumerate() {
+        w.push_str(if i == 0 { " RETURNING " } else { ", " });
+        column.write_sql(w)?;
+    }
+    Ok(())
+}
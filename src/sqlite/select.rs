@@ -1,18 +1,64 @@
-use super::BuildableStatement;
+use super::{
+    write_order, write_table, write_with, BuildableStatement, Compound, Expr, Ident,
+    IntoColumnExpr, IntoCondition, IntoResultColumns, Join, JoinClause, OrderTerm, Parser,
+    Placeholder, Quoting, ResultColumn, SqlWriter, TableName, Value, Window, With, WriteSql,
+};
 use crate::errors::SqlBuilderError;
 
+/// Represents the source of a `FROM` or `JOIN`: a named table or an aliased subquery.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TableRef {
+    Named(TableName),
+    /// A table reference `TableName::parse` rejects, kept to report the error when built.
+    Invalid(String),
+    Subquery(Box<Compound>, String),
+}
+
+impl TableRef {
+    /// Creates a reference to a table written as `name`, `name alias` or `name AS alias`.
+    pub(crate) fn named(table: &str) -> Self {
+        match TableName::parse(table) {
+            Ok(table) => Self::Named(table),
+            Err(_) => Self::Invalid(table.to_string()),
+        }
+    }
+}
+
+impl WriteSql for TableRef {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        match self {
+            Self::Named(table) => table.write_sql(w)?,
+            Self::Invalid(table) => write_table(w, table)?,
+            Self::Subquery(query, alias) => {
+                if alias.is_empty() {
+                    return Err(SqlBuilderError::EmptyTableName);
+                }
+                w.push('(');
+                query.write_sql(w)?;
+                w.push_str(") ");
+                Ident::unqualified(alias).write_sql(w)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Represents the creation of a SELECT with specified table and options.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Select {
-    table: String,
+    with: Option<With>,
+    table: TableRef,
     distinct: bool,
-    condition: Option<String>,
-    columns: Option<String>,
-    group: Option<String>,
-    order: Option<String>,
+    condition: Option<Expr>,
+    columns: Vec<ResultColumn>,
+    group: Vec<Expr>,
+    having: Option<Expr>,
+    windows: Vec<(String, Window)>,
+    order: Vec<OrderTerm>,
     limit: Option<u32>,
     offset: Option<u32>,
-    join: Option<Vec<String>>,
+    join: Vec<JoinClause>,
+    quoting: Option<Quoting>,
 }
 
 impl Select {
@@ -24,86 +70,69 @@ impl Select {
     /// assert_eq!(select, "SELECT name, age FROM users;")
     /// ```
     pub fn new(table: &str) -> Self {
+        Self::from_table(TableRef::named(table))
+    }
+
+    /// Creates a new `Select` instance that reads from a subquery, used as a derived table.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Select};
+    ///
+    /// let mut adults = Select::new("users");
+    /// adults.condition(Expr::col("age").greater_than_equal(18));
+    ///
+    /// let select = Select::from_subquery(adults, "a").columns("a.name").build().unwrap();
+    /// assert_eq!(select, "SELECT a.name FROM (SELECT * FROM users WHERE age >= 18) a;");
+    /// ```
+    pub fn from_subquery(query: impl Into<Compound>, alias: &str) -> Self {
+        Self::from_table(TableRef::Subquery(
+            Box::new(query.into()),
+            alias.to_string(),
+        ))
+    }
+
+    fn from_table(table: TableRef) -> Self {
         Self {
-            table: table.to_string(),
+            with: None,
+            table,
             distinct: false,
-            columns: None,
+            columns: Vec::new(),
             condition: None,
-            group: None,
-            order: None,
+            group: Vec::new(),
+            having: None,
+            windows: Vec::new(),
+            order: Vec::new(),
             limit: None,
             offset: None,
-            join: None,
+            join: Vec::new(),
+            quoting: None,
         }
     }
 
     /// Creates a new `Select` instance from a SQL query string.
-    /// The query string should be in the format "SELECT * FROM table_name [WHERE condition] [GROUP BY column] [ORDER BY column] [LIMIT limit] [OFFSET offset]".
+    ///
+    /// The query is tokenized and parsed following SQLite's `SELECT` grammar, so column lists,
+    /// joins, multi-token conditions, quoted identifiers and string literals are all kept.
+    /// Parse errors report the byte position and the token that was expected.
+    ///
+    /// Comma joins, `NATURAL` joins, `JOIN ... USING`, the `COLLATE` operator inside an
+    /// expression and `LIKE ... ESCAPE` cannot be represented by the builders, so they fail
+    /// with `SqlBuilderError::Unsupported` at the position of the unsupported syntax.
     /// # Example
     /// ```
     /// use lumus_sql_builder::sqlite::Select;
-    /// let query = "SELECT * FROM users WHERE age > 18 GROUP BY city ORDER BY name LIMIT 10 OFFSET 0";
-    /// Select::from(query);
+    /// let query = "SELECT name, age FROM users WHERE age > 18 AND city = 'São Paulo' ORDER BY name LIMIT 10";
+    /// let select = Select::from(query).unwrap();
+    /// assert_eq!(select.build().unwrap(), format!("{};", query));
     /// ```
     pub fn from(query: &str) -> Result<Select, SqlBuilderError> {
-        let mut parts = query.split_whitespace();
-        let select = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-        if select.to_uppercase() != "SELECT" {
-            return Err(SqlBuilderError::InvalidQuery);
-        }
-
-        let _ = parts.next().ok_or(SqlBuilderError::InvalidQuery)?; // Skip the "*"
-
-        let from = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-        if from.to_uppercase() != "FROM" {
-            return Err(SqlBuilderError::InvalidQuery);
-        }
-
-        let table = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-
-        let mut select_builder = Select::new(table);
-
-        while let Some(part) = parts.next() {
-            match part.to_uppercase().as_str() {
-                "WHERE" => {
-                    let condition = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-                    select_builder.condition(condition.to_string());
-                }
-                "GROUP" => {
-                    let by = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-                    if by.to_uppercase() != "BY" {
-                        return Err(SqlBuilderError::InvalidQuery);
-                    }
-                    let group = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-                    select_builder.group(group);
-                }
-                "ORDER" => {
-                    let by = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-                    if by.to_uppercase() != "BY" {
-                        return Err(SqlBuilderError::InvalidQuery);
-                    }
-                    let order = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-                    select_builder.order(order);
-                }
-                "LIMIT" => {
-                    let limit = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-                    let limit = limit
-                        .parse::<u32>()
-                        .map_err(|_| SqlBuilderError::InvalidQuery)?;
-                    select_builder.limit(limit);
-                }
-                "OFFSET" => {
-                    let offset = parts.next().ok_or(SqlBuilderError::InvalidQuery)?;
-                    let offset = offset
-                        .parse::<u32>()
-                        .map_err(|_| SqlBuilderError::InvalidQuery)?;
-                    select_builder.offset(offset);
-                }
-                _ => return Err(SqlBuilderError::InvalidQuery),
-            }
-        }
+        Parser::parse(query, Parser::parse_select)
+    }
 
-        Ok(select_builder)
+    /// Prefixes the query with a `WITH` clause.
+    pub fn with(&mut self, with: With) -> &mut Self {
+        self.with = Some(with);
+        self
     }
 
     /// Specifies that the select statement should return distinct rows.
@@ -112,27 +141,161 @@ impl Select {
         self
     }
 
-    /// Specifies the columns to be selected in the query.
-    pub fn columns(&mut self, columns: &str) -> &mut Self {
-        self.columns = Some(columns.to_string());
+    /// Adds columns to be selected in the query.
+    ///
+    /// A `&str` is added as raw SQL, such as `"name, age"`; arrays and vectors add one
+    /// column per item.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, ResultColumn, Select};
+    ///
+    /// let mut select = Select::new("users");
+    /// select
+    ///     .columns([Expr::col("id"), Expr::col("first name")])
+    ///     .columns([ResultColumn::aliased(Expr::col("group"), "team")]);
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "SELECT id, \"first name\", \"group\" AS team FROM users;"
+    /// );
+    /// ```
+    pub fn columns(&mut self, columns: impl IntoResultColumns) -> &mut Self {
+        self.columns.extend(columns.into_result_columns());
+        self
+    }
+
+    /// Adds a single column or expression to be selected in the query.
+    pub fn column(&mut self, column: impl Into<ResultColumn>) -> &mut Self {
+        self.columns.push(column.into());
+        self
+    }
+
+    /// Adds an expression, such as a scalar subquery, to the columns under the given alias.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Select};
+    ///
+    /// let mut orders = Select::new("orders");
+    /// orders
+    ///     .columns("count(*)")
+    ///     .condition(Expr::col("orders.user_id").equal_to(Expr::col("users.id")));
+    ///
+    /// let mut select = Select::new("users");
+    /// select.columns("name").column_as(Expr::subquery(orders), "total");
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "SELECT name, (SELECT count(*) FROM orders WHERE orders.user_id = users.id) AS total FROM users;"
+    /// );
+    /// ```
+    pub fn column_as(&mut self, expr: impl IntoColumnExpr, alias: &str) -> &mut Self {
+        self.columns.push(ResultColumn::aliased(expr, alias));
+        self
+    }
+
+    /// Adds every column of the given table, as `table.*`.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Join, JoinType, Select};
+    ///
+    /// let mut select = Select::new("users");
+    /// select
+    ///     .all_from("users")
+    ///     .column_as(Expr::col("orders.total"), "order total")
+    ///     .join_clause(Join::new(
+    ///         "orders",
+    ///         JoinType::Left,
+    ///         Expr::col("orders.user_id").equal_to(Expr::col("users.id")),
+    ///     ));
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "SELECT users.*, orders.total AS \"order total\" FROM users LEFT JOIN orders ON orders.user_id = users.id;"
+    /// );
+    /// ```
+    pub fn all_from(&mut self, table: &str) -> &mut Self {
+        self.columns.push(ResultColumn::all_from(table));
         self
     }
 
     /// Specifies the grouping for the query results.
     pub fn group(&mut self, group: &str) -> &mut Self {
-        self.group = Some(group.to_string());
+        self.group = vec![Expr::raw(group)];
+        self
+    }
+
+    /// Adds expressions to the grouping for the query results.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Select};
+    ///
+    /// let mut select = Select::new("orders");
+    /// select
+    ///     .columns("user_id, status, count(*)")
+    ///     .group_by([Expr::col("user_id"), Expr::col("status")])
+    ///     .having(Expr::func("count", vec![Expr::raw("*")]).greater_than(5));
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "SELECT user_id, status, count(*) FROM orders GROUP BY user_id, status HAVING count(*) > 5;"
+    /// );
+    /// ```
+    pub fn group_by<I, E>(&mut self, group: I) -> &mut Self
+    where
+        I: IntoIterator<Item = E>,
+        E: IntoColumnExpr,
+    {
+        self.group
+            .extend(group.into_iter().map(IntoColumnExpr::into_column_expr));
+        self
+    }
+
+    /// Specifies the condition on grouped rows, as `HAVING`.
+    pub fn having(&mut self, condition: impl IntoCondition) -> &mut Self {
+        self.having = Some(condition.into_condition());
+        self
+    }
+
+    /// Adds a named window to the `WINDOW` clause, for use with `Expr::over_named`.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Select, Window};
+    ///
+    /// let mut select = Select::new("sales");
+    /// select
+    ///     .columns("day")
+    ///     .column_as(Expr::func("sum", vec![Expr::col("amount")]).over_named("w"), "running")
+    ///     .window("w", Window::new().order_by([Expr::col("day")]));
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "SELECT day, sum(amount) OVER w AS running FROM sales WINDOW w AS (ORDER BY day);"
+    /// );
+    /// ```
+    pub fn window(&mut self, name: &str, window: Window) -> &mut Self {
+        self.windows.push((name.to_string(), window));
         self
     }
 
     /// Specifies the ordering for the query results.
     pub fn order(&mut self, order: &str) -> &mut Self {
-        self.order = Some(order.to_string());
+        self.order = vec![OrderTerm::new(Expr::raw(order))];
+        self
+    }
+
+    /// Adds terms to the ordering for the query results.
+    pub fn order_by<I, T>(&mut self, order: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OrderTerm>,
+    {
+        self.order.extend(order.into_iter().map(Into::into));
         self
     }
 
     /// Specifies where for `Select`.
-    pub fn condition(&mut self, condition: String) -> &mut Self {
-        self.condition = Some(condition);
+    pub fn condition(&mut self, condition: impl IntoCondition) -> &mut Self {
+        self.condition = Some(condition.into_condition());
         self
     }
 
@@ -150,62 +313,189 @@ impl Select {
 
     /// Specifies a join.
     pub fn join(&mut self, join: String) -> &mut Self {
-        match &mut self.join {
-            None => self.join = Some(vec![join]),
-            Some(j) => j.push(join),
-        }
+        self.join.push(JoinClause::Built(join));
+        self
+    }
+
+    /// Specifies a join kept as a builder, so its table and `ON` condition are rendered
+    /// with the rest of the query and their values can be bound as parameters.
+    pub fn join_clause(&mut self, join: Join) -> &mut Self {
+        self.join.push(JoinClause::Join(join));
+        self
+    }
 
+    /// Sets when the names of the statement are written between quotes, for names that do
+    /// not set their own policy.
+    pub fn quoting(&mut self, quoting: Quoting) -> &mut Self {
+        self.quoting = Some(quoting);
         self
     }
 
     /// Builds and returns the SQL statement for the select query.
     pub fn build(&self) -> Result<String, SqlBuilderError> {
-        if self.table.is_empty() {
-            return Err(SqlBuilderError::EmptyTableName);
+        Ok(format!("{};", self.render()?))
+    }
+
+    /// Builds the SQL statement with placeholders and returns the values to bind.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Placeholder, Select, Value, Where};
+    ///
+    /// let mut condition = Where::new();
+    /// condition.equal_to("name", "Dayvson Spacca");
+    ///
+    /// let (sql, params) = Select::new("users")
+    ///     .condition(condition)
+    ///     .build_with_params(Placeholder::Numbered)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(sql, "SELECT * FROM users WHERE name = ?1;");
+    /// assert_eq!(params, vec![Value::from("Dayvson Spacca")]);
+    /// ```
+    pub fn build_with_params(
+        &self,
+        placeholder: Placeholder,
+    ) -> Result<(String, Vec<Value>), SqlBuilderError> {
+        let (sql, params) = self.render_with_params(placeholder)?;
+        Ok((format!("{};", sql), params))
+    }
+}
+
+impl Select {
+    /// Returns `true` if a `WHERE` clause has been set.
+    pub(crate) fn has_condition(&self) -> bool {
+        self.condition.is_some()
+    }
+
+    /// Returns `true` if a `WITH` clause has been set.
+    pub(crate) fn has_with(&self) -> bool {
+        self.with.is_some()
+    }
+
+    /// Returns `true` if an `ORDER BY`, `LIMIT` or `OFFSET` clause has been set.
+    pub(crate) fn has_order_or_limit(&self) -> bool {
+        !self.order.is_empty() || self.limit.is_some() || self.offset.is_some()
+    }
+
+    /// Removes and returns the `ORDER BY`, `LIMIT` and `OFFSET` clauses.
+    pub(crate) fn take_order_and_limit(&mut self) -> (Vec<OrderTerm>, Option<u32>, Option<u32>) {
+        (
+            std::mem::take(&mut self.order),
+            self.limit.take(),
+            self.offset.take(),
+        )
+    }
+
+    /// Returns the number of result columns, or `None` when it depends on a `*`.
+    pub(crate) fn column_count(&self) -> Option<usize> {
+        if self.columns.is_empty() {
+            return None;
+        }
+
+        let mut count = 0;
+        for column in &self.columns {
+            count += match column {
+                ResultColumn::Expr(Expr::Raw(sql), None) => {
+                    let columns =
+                        Parser::parse(sql, |p| p.parse_list(Parser::parse_result_column)).ok()?;
+                    if columns
+                        .iter()
+                        .any(|col| !matches!(col, ResultColumn::Expr(..)))
+                    {
+                        return None;
+                    }
+                    columns.len()
+                }
+                ResultColumn::Expr(..) => 1,
+                ResultColumn::All | ResultColumn::AllFrom(_) => return None,
+            };
         }
+        Some(count)
+    }
 
-        let mut statement = String::from("SELECT");
+    /// Internal method to write the statement under the quoting policy set on the writer.
+    fn write_statement(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        write_with(w, &self.with)?;
+        w.push_str("SELECT");
 
         if self.distinct {
-            statement.push_str(" DISTINCT");
+            w.push_str(" DISTINCT");
         }
 
-        if let Some(columns) = &self.columns {
-            statement.push_str(&format!(" {}", columns));
-        } else {
-            statement.push_str(" *");
+        if self.columns.is_empty() {
+            w.push_str(" *");
+        }
+        for (i, column) in self.columns.iter().enumerate() {
+            w.push_str(if i == 0 { " " } else { ", " });
+            column.write_sql(w)?;
         }
 
-        statement.push_str(&format!(" FROM {}", self.table));
+        w.push_str(" FROM ");
+        self.table.write_sql(w)?;
 
-        if let Some(join) = &self.join {
-            for j in join {
-                statement.push_str(&format!(" {}", j))
-            }
+        for join in &self.join {
+            w.push(' ');
+            join.write_sql(w)?;
         }
 
         if let Some(condition) = &self.condition {
-            statement.push_str(&format!(" WHERE {}", condition));
+            if condition.is_empty() {
+                return Err(SqlBuilderError::EmptyCondition);
+            }
+            w.push_str(" WHERE ");
+            condition.write_sql(w)?;
+        }
+
+        for (i, expr) in self.group.iter().enumerate() {
+            if expr.is_empty() {
+                return Err(SqlBuilderError::EmptyColumnName);
+            }
+            w.push_str(if i == 0 { " GROUP BY " } else { ", " });
+            expr.write_sql(w)?;
         }
 
-        if let Some(group) = &self.group {
-            statement.push_str(&format!(" GROUP BY {}", group));
+        if let Some(having) = &self.having {
+            if self.group.is_empty()
+                && !having.has_aggregate()
+                && !self.columns.iter().any(ResultColumn::has_aggregate)
+            {
+                return Err(SqlBuilderError::HavingWithoutGroupBy);
+            }
+            if having.is_empty() {
+                return Err(SqlBuilderError::EmptyCondition);
+            }
+            w.push_str(" HAVING ");
+            having.write_sql(w)?;
         }
 
-        if let Some(order) = &self.order {
-            statement.push_str(&format!(" ORDER BY {}", order));
+        for (i, (name, window)) in self.windows.iter().enumerate() {
+            if name.is_empty() {
+                return Err(SqlBuilderError::InvalidQuery);
+            }
+            w.push_str(if i == 0 { " WINDOW " } else { ", " });
+            Ident::unqualified(name).write_sql(w)?;
+            w.push_str(" AS (");
+            window.write_sql(w)?;
+            w.push(')');
         }
 
+        write_order(w, &self.order)?;
+
         if let Some(limit) = &self.limit {
-            statement.push_str(&format!(" LIMIT {}", limit));
+            w.push_str(&format!(" LIMIT {}", limit));
         }
 
         if let Some(offset) = &self.offset {
-            statement.push_str(&format!(" OFFSET {}", offset));
+            w.push_str(&format!(" OFFSET {}", offset));
         }
 
-        statement.push(';');
-        Ok(statement)
+        Ok(())
+    }
+}
+
+impl WriteSql for Select {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        w.with_quoting(self.quoting, |w| self.write_statement(w))
     }
 }
 
@@ -0,0 +1,224 @@
+use crate::errors::SqlBuilderError;
+
+/// Represents the kinds of token produced by the tokenizer.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TokenKind {
+    /// A bare word, which may be a keyword or an identifier.
+    Word(String),
+    /// An identifier written between `"`, `` ` `` or `[` `]`.
+    QuotedIdent(String),
+    String(String),
+    Blob(Vec<u8>),
+    Number(String),
+    /// A bind parameter such as `?`, `?1`, `:name`, `@name` or `$name`.
+    Variable(String),
+    Symbol(&'static str),
+    Eof,
+}
+
+impl TokenKind {
+    /// Returns a human readable description of the token for error messages.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Self::Word(w) => format!("'{}'", w),
+            Self::QuotedIdent(i) => format!("\"{}\"", i),
+            Self::String(s) => format!("string '{}'", s),
+            Self::Blob(_) => "blob literal".to_string(),
+            Self::Number(n) => format!("number {}", n),
+            Self::Variable(v) => format!("parameter {}", v),
+            Self::Symbol(s) => format!("'{}'", s),
+            Self::Eof => "end of input".to_string(),
+        }
+    }
+}
+
+/// Represents a token and the byte offset where it starts in the input.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    pub(crate) position: usize,
+}
+
+const SYMBOLS: [&str; 27] = [
+    "->>", "||", "->", "==", "!=", "<>", "<=", ">=", "<<", ">>", "(", ")", ",", ";", ".", "*", "+",
+    "-", "/", "%", "=", "<", ">", "&", "|", "~", "?",
+];
+
+/// Splits a SQL string into tokens, skipping whitespace and comments.
+pub(crate) fn tokenize(sql: &str) -> Result<Vec<Token>, SqlBuilderError> {
+    let chars = sql.char_indices().collect::<Vec<_>>();
+    let offset = |i: usize| chars.get(i).map_or(sql.len(), |(o, _)| *o);
+    let unterminated = |i: usize, expected: &str| SqlBuilderError::UnexpectedToken {
+        position: offset(i),
+        expected: expected.to_string(),
+        found: TokenKind::Eof.describe(),
+    };
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i].1;
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c == '-' && next == Some('-') {
+            while i < chars.len() && chars[i].1 != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+
+        if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len()
+                && !(chars[i].1 == '*' && chars.get(i + 1).map(|c| c.1) == Some('/'))
+            {
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(unterminated(i, "'*/'"));
+            }
+            i += 2;
+            continue;
+        }
+
+        let kind = if (c == 'x' || c == 'X') && next == Some('\'') {
+            let (text, end) = read_quoted(&chars, i + 1, '\'').ok_or(unterminated(i, "'"))?;
+            i = end;
+            let blob = decode_hex(&text).ok_or(SqlBuilderError::UnexpectedToken {
+                position: offset(start),
+                expected: "hexadecimal blob".to_string(),
+                found: format!("X'{}'", text),
+            })?;
+            TokenKind::Blob(blob)
+        } else if c == '\'' {
+            let (text, end) = read_quoted(&chars, i, '\'').ok_or(unterminated(i, "'"))?;
+            i = end;
+            TokenKind::String(text)
+        } else if c == '"' || c == '`' {
+            let (text, end) = read_quoted(&chars, i, c).ok_or(unterminated(i, &c.to_string()))?;
+            i = end;
+            TokenKind::QuotedIdent(text)
+        } else if c == '[' {
+            let end = (i..chars.len())
+                .find(|&j| chars[j].1 == ']')
+                .ok_or(unterminated(i, "']'"))?;
+            let text = chars[i + 1..end].iter().map(|(_, c)| c).collect();
+            i = end + 1;
+            TokenKind::QuotedIdent(text)
+        } else if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) {
+            i = read_number(&chars, i);
+            TokenKind::Number(chars[start..i].iter().map(|(_, c)| c).collect())
+        } else if c == '?' || ((c == ':' || c == '@' || c == '$') && next.is_some_and(is_word_char))
+        {
+            i += 1;
+            while i < chars.len() && is_word_char(chars[i].1) {
+                i += 1;
+            }
+            TokenKind::Variable(chars[start..i].iter().map(|(_, c)| c).collect())
+        } else if is_word_char(c) {
+            while i < chars.len() && (is_word_char(chars[i].1) || chars[i].1 == '$') {
+                i += 1;
+            }
+            TokenKind::Word(chars[start..i].iter().map(|(_, c)| c).collect())
+        } else {
+            let rest = &sql[offset(i)..];
+            let symbol = SYMBOLS.iter().find(|s| rest.starts_with(*s)).ok_or(
+                SqlBuilderError::UnexpectedToken {
+                    position: offset(i),
+                    expected: "token".to_string(),
+                    found: format!("'{}'", c),
+                },
+            )?;
+            i += symbol.chars().count();
+            TokenKind::Symbol(symbol)
+        };
+
+        tokens.push(Token {
+            kind,
+            position: offset(start),
+        });
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::Eof,
+        position: sql.len(),
+    });
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Reads text between `quote` characters starting at `start`, where a doubled quote is an escape.
+fn read_quoted(chars: &[(usize, char)], start: usize, quote: char) -> Option<(String, usize)> {
+    let mut text = String::new();
+    let mut i = start + 1;
+    loop {
+        let c = chars.get(i)?.1;
+        if c == quote {
+            if chars.get(i + 1).map(|(_, c)| *c) == Some(quote) {
+                text.push(quote);
+                i += 2;
+                continue;
+            }
+            return Some((text, i + 1));
+        }
+        text.push(c);
+        i += 1;
+    }
+}
+
+/// Returns the index just past the numeric literal starting at `start`.
+fn read_number(chars: &[(usize, char)], start: usize) -> usize {
+    let at = |i: usize| chars.get(i).map(|(_, c)| *c);
+    let mut i = start;
+
+    if at(i) == Some('0') && matches!(at(i + 1), Some('x' | 'X')) {
+        i += 2;
+        while at(i).is_some_and(|c| c.is_ascii_hexdigit()) {
+            i += 1;
+        }
+        return i;
+    }
+
+    while at(i).is_some_and(|c| c.is_ascii_digit()) {
+        i += 1;
+    }
+    if at(i) == Some('.') {
+        i += 1;
+        while at(i).is_some_and(|c| c.is_ascii_digit()) {
+            i += 1;
+        }
+    }
+    if matches!(at(i), Some('e' | 'E')) {
+        let mut j = i + 1;
+        if matches!(at(j), Some('+' | '-')) {
+            j += 1;
+        }
+        if at(j).is_some_and(|c| c.is_ascii_digit()) {
+            i = j;
+            while at(i).is_some_and(|c| c.is_ascii_digit()) {
+                i += 1;
+            }
+        }
+    }
+    i
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
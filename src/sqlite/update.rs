@@ -1,12 +1,24 @@
-use super::BuildableStatement;
+use super::{
+    write_order, write_returning, write_table, write_with, BuildableStatement, Expr, Ident,
+    IntoCondition, Join, JoinClause, OrderTerm, Parser, Placeholder, Quoting, ResultColumn,
+    SqlWriter, Value, With, WriteSql,
+};
 use crate::errors::SqlBuilderError;
 
 /// Represents a ´UPDATE´ clause builder for SQL queries
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Update {
+    with: Option<With>,
     table: String,
-    pub set: Vec<(String, String)>,
-    condition: Option<String>,
+    pub set: Vec<(String, Expr)>,
+    from: Option<String>,
+    join: Vec<JoinClause>,
+    condition: Option<Expr>,
+    returning: Vec<ResultColumn>,
+    order: Vec<OrderTerm>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+    quoting: Option<Quoting>,
 }
 
 impl Update {
@@ -20,35 +32,200 @@ impl Update {
     ///
     /// let update = Update::new("users_tb").set(vec![
     ///     ("name", "João")
-    /// ]).condition(condition.build())
+    /// ]).condition(condition)
     /// .build();
     ///
     /// assert_eq!("UPDATE users_tb SET name = 'João' WHERE age = '21';", update.unwrap());
     /// ```
     pub fn new(table: &str) -> Self {
         Self {
+            with: None,
             table: table.to_string(),
             set: Vec::new(),
+            from: None,
+            join: Vec::new(),
             condition: None,
+            returning: Vec::new(),
+            order: Vec::new(),
+            limit: None,
+            offset: None,
+            quoting: None,
         }
     }
 
+    /// Creates a new `Update` instance from a SQL statement string.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::Update;
+    ///
+    /// let query = "UPDATE users SET name = 'João', age = 30 WHERE id = 7;";
+    /// let update = Update::from(query).unwrap();
+    /// assert_eq!(update.build().unwrap(), query);
+    /// ```
+    pub fn from(statement: &str) -> Result<Update, SqlBuilderError> {
+        Parser::parse(statement, Parser::parse_update)
+    }
+
     /// Sets the values to be updated.
-    pub fn set<T: ToString>(mut self, set: Vec<(&str, T)>) -> Self {
+    pub fn set<T: Into<Value>>(mut self, set: Vec<(&str, T)>) -> Self {
         self.set = set
             .into_iter()
-            .map(|(col, val)| (col.to_string(), val.to_string()))
+            .map(|(col, val)| (col.to_string(), Expr::Value(val.into())))
             .collect();
         self
     }
 
+    /// Adds a column assigned to an expression, such as `Expr::col("counter") + 1`; SQL
+    /// keywords like `CURRENT_TIMESTAMP` are written with `Expr::raw`.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Update, Value};
+    ///
+    /// let update = Update::new("posts")
+    ///     .set_expr("views", Expr::col("views") + 1)
+    ///     .set_expr("updated_at", Expr::raw("CURRENT_TIMESTAMP"))
+    ///     .set_expr("deleted_at", Value::Null)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     update,
+    ///     "UPDATE posts SET views = views + 1, updated_at = CURRENT_TIMESTAMP, deleted_at = NULL;"
+    /// );
+    /// ```
+    pub fn set_expr(mut self, column: &str, expr: impl Into<Expr>) -> Self {
+        self.set.push((column.to_string(), expr.into()));
+        self
+    }
+
+    /// Specifies the table of an `UPDATE ... FROM` clause.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Update};
+    ///
+    /// let mut update = Update::new("inventory")
+    ///     .set_expr("quantity", Expr::col("quantity") - Expr::col("daily.amt"));
+    /// update
+    ///     .from_table("daily")
+    ///     .condition(Expr::col("inventory.item_id").equal_to(Expr::col("daily.item_id")));
+    ///
+    /// assert_eq!(
+    ///     update.build().unwrap(),
+    ///     "UPDATE inventory SET quantity = quantity - daily.amt FROM daily \
+    ///      WHERE inventory.item_id = daily.item_id;"
+    /// );
+    /// ```
+    pub fn from_table(&mut self, table: &str) -> &mut Self {
+        self.from = Some(table.to_string());
+        self
+    }
+
+    /// Specifies a join on the tables of the `FROM` clause.
+    pub fn join(&mut self, join: String) -> &mut Self {
+        self.join.push(JoinClause::Built(join));
+        self
+    }
+
+    /// Specifies a join on the tables of the `FROM` clause, kept as a builder so its values
+    /// can be bound as parameters.
+    pub fn join_clause(&mut self, join: Join) -> &mut Self {
+        self.join.push(JoinClause::Join(join));
+        self
+    }
+
+    /// Prefixes the statement with a `WITH` clause.
+    pub fn with(&mut self, with: With) -> &mut Self {
+        self.with = Some(with);
+        self
+    }
+
     /// Specifies where for `Update`.
-    pub fn condition(&mut self, condition: String) -> &mut Self {
-        self.condition = Some(condition);
+    pub fn condition(&mut self, condition: impl IntoCondition) -> &mut Self {
+        self.condition = Some(condition.into_condition());
+        self
+    }
+
+    /// Adds columns to the `RETURNING` clause, where a string names a column and
+    /// `ResultColumn::All` renders `*`.
+    pub fn returning<I, C>(&mut self, columns: I) -> &mut Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<ResultColumn>,
+    {
+        self.returning.extend(columns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Specifies the ordering of the rows a `LIMIT` applies to.
+    pub fn order(&mut self, order: &str) -> &mut Self {
+        self.order = vec![OrderTerm::new(Expr::raw(order))];
+        self
+    }
+
+    /// Adds terms to the ordering of the rows a `LIMIT` applies to.
+    pub fn order_by<I, T>(&mut self, order: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OrderTerm>,
+    {
+        self.order.extend(order.into_iter().map(Into::into));
+        self
+    }
+
+    /// Specifies the maximum number of rows to be updated.
+    ///
+    /// Requires the `update-delete-limit` feature, as SQLite only accepts it when built with
+    /// `SQLITE_ENABLE_UPDATE_DELETE_LIMIT`.
+    pub fn limit(&mut self, limit: u32) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Specifies the number of rows skipped before the `LIMIT` applies.
+    pub fn offset(&mut self, offset: u32) -> &mut Self {
+        self.offset = Some(offset);
         self
     }
 
+    /// Sets when the names of the statement are written between quotes, for names that do
+    /// not set their own policy.
+    pub fn quoting(&mut self, quoting: Quoting) -> &mut Self {
+        self.quoting = Some(quoting);
+        self
+    }
+
+    /// Builds and returns the SQL statement for the `UPDATE` query.
     pub fn build(&self) -> Result<String, SqlBuilderError> {
+        Ok(format!("{};", self.render()?))
+    }
+
+    /// Builds the SQL statement with placeholders and returns the values to bind.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Placeholder, Update, Value, Where};
+    ///
+    /// let mut condition = Where::new();
+    /// condition.equal_to("age", "21");
+    ///
+    /// let (sql, params) = Update::new("users_tb")
+    ///     .set(vec![("name", "João")])
+    ///     .condition(condition)
+    ///     .build_with_params(Placeholder::Numbered)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(sql, "UPDATE users_tb SET name = ?1 WHERE age = ?2;");
+    /// assert_eq!(params, vec![Value::from("João"), Value::from("21")]);
+    /// ```
+    pub fn build_with_params(
+        &self,
+        placeholder: Placeholder,
+    ) -> Result<(String, Vec<Value>), SqlBuilderError> {
+        let (sql, params) = self.render_with_params(placeholder)?;
+        Ok((format!("{};", sql), params))
+    }
+
+    /// Internal method to write the statement under the quoting policy set on the writer.
+    fn write_statement(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
         if self.table.is_empty() {
             return Err(SqlBuilderError::EmptyTableName);
         }
@@ -57,29 +234,81 @@ impl Update {
             return Err(SqlBuilderError::EmptyColumnAndValue);
         }
 
-        let mut sets: Vec<String> = vec![];
-
         for (col, val) in &self.set {
             if col.is_empty() {
                 return Err(SqlBuilderError::EmptyColumnName);
             }
-            if val.is_empty() {
+            if val.is_empty() || matches!(val, Expr::Value(v) if v.is_empty()) {
                 return Err(SqlBuilderError::EmptyValue);
             }
+        }
 
-            sets.push(format!("{} = '{}'", col.clone(), val.clone()));
+        if self.from.is_none() && !self.join.is_empty() {
+            return Err(SqlBuilderError::InvalidQuery);
+        }
+
+        if !self.order.is_empty() || self.limit.is_some() || self.offset.is_some() {
+            if !cfg!(feature = "update-delete-limit") {
+                return Err(SqlBuilderError::UpdateDeleteLimitDisabled);
+            }
+            if self.limit.is_none() {
+                return Err(SqlBuilderError::InvalidQuery);
+            }
+        }
+
+        write_with(w, &self.with)?;
+        w.push_str("UPDATE ");
+        write_table(w, &self.table)?;
+        w.push_str(" SET ");
+        for (i, (col, val)) in self.set.iter().enumerate() {
+            if i > 0 {
+                w.push_str(", ");
+            }
+            Ident::parse(col).write_sql(w)?;
+            w.push_str(" = ");
+            val.write_sql(w)?;
+        }
+
+        if let Some(from) = &self.from {
+            if from.is_empty() {
+                return Err(SqlBuilderError::EmptyTableName);
+            }
+            w.push_str(" FROM ");
+            write_table(w, from)?;
+        }
+
+        for join in &self.join {
+            w.push(' ');
+            join.write_sql(w)?;
         }
 
         if let Some(condition) = &self.condition {
-            return Ok(format!(
-                "UPDATE {} SET {} WHERE {};",
-                self.table,
-                sets.join(", "),
-                condition
-            ));
+            if condition.is_empty() {
+                return Err(SqlBuilderError::EmptyCondition);
+            }
+            w.push_str(" WHERE ");
+            condition.write_sql(w)?;
+        }
+
+        write_returning(w, &self.returning)?;
+
+        write_order(w, &self.order)?;
+
+        if let Some(limit) = &self.limit {
+            w.push_str(&format!(" LIMIT {}", limit));
         }
 
-        Ok(format!("UPDATE {} SET {};", self.table, sets.join(", "),))
+        if let Some(offset) = &self.offset {
+            w.push_str(&format!(" OFFSET {}", offset));
+        }
+
+        Ok(())
+    }
+}
+
+impl WriteSql for Update {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        w.with_quoting(self.quoting, |w| self.write_statement(w))
     }
 }
 
@@ -0,0 +1,205 @@
+use super::{
+    write_names, BuildableStatement, Expr, Ident, IntoCondition, SqlWriter, Value, WriteSql,
+};
+use crate::errors::SqlBuilderError;
+
+/// Represents the conflict resolution algorithm of an `INSERT OR ...` statement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictResolution {
+    Rollback,
+    Abort,
+    Fail,
+    Ignore,
+    Replace,
+}
+
+/// Implementation of the `BuildableStatement` trait for `ConflictResolution`, allowing it to be printed.
+impl BuildableStatement for ConflictResolution {
+    fn build(&self) -> String {
+        String::from(match self {
+            Self::Rollback => "ROLLBACK",
+            Self::Abort => "ABORT",
+            Self::Fail => "FAIL",
+            Self::Ignore => "IGNORE",
+            Self::Replace => "REPLACE",
+        })
+    }
+}
+
+/// Represents what an upsert does when a row conflicts with an existing one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictAction {
+    /// `DO NOTHING`
+    Nothing,
+    /// `DO UPDATE SET ...`
+    Update { set: Vec<(String, Expr)> },
+}
+
+/// Represents an `ON CONFLICT` upsert clause of an `INSERT` statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnConflict {
+    target: Vec<String>,
+    target_condition: Option<Expr>,
+    action: ConflictAction,
+    update_condition: Option<Expr>,
+}
+
+impl OnConflict {
+    /// Creates a new `OnConflict` clause without a conflict target that does nothing.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Insert, OnConflict};
+    ///
+    /// let insert = Insert::new("users")
+    ///     .values(vec![("email", "joao@mail.com"), ("name", "João")])
+    ///     .on_conflict(
+    ///         OnConflict::new()
+    ///             .target(["email"])
+    ///             .do_update_expr("name", Expr::excluded("name")),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     insert,
+    ///     "INSERT INTO users (email, name) VALUES ('joao@mail.com', 'João') \
+    ///      ON CONFLICT (email) DO UPDATE SET name = excluded.name;"
+    /// );
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            target: Vec::new(),
+            target_condition: None,
+            action: ConflictAction::Nothing,
+            update_condition: None,
+        }
+    }
+
+    /// Sets the columns of the unique index the conflict is detected on.
+    pub fn target<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.target = columns
+            .into_iter()
+            .map(|col| col.as_ref().to_string())
+            .collect();
+        self
+    }
+
+    /// Specifies the `WHERE` of a partial index used as conflict target.
+    pub fn target_condition(mut self, condition: impl IntoCondition) -> Self {
+        self.target_condition = Some(condition.into_condition());
+        self
+    }
+
+    /// Skips the conflicting row with `DO NOTHING`.
+    pub fn do_nothing(mut self) -> Self {
+        self.action = ConflictAction::Nothing;
+        self
+    }
+
+    /// Updates the existing row with `DO UPDATE SET`, replacing any previous action.
+    pub fn do_update<T: Into<Value>>(mut self, set: Vec<(&str, T)>) -> Self {
+        self.action = ConflictAction::Update {
+            set: set
+                .into_iter()
+                .map(|(col, val)| (col.to_string(), Expr::value(val)))
+                .collect(),
+        };
+        self
+    }
+
+    /// Adds a column assigned to an expression, such as `excluded.name`, to the `DO UPDATE SET`
+    /// action, turning a `DO NOTHING` action into an update.
+    pub fn do_update_expr(mut self, column: &str, expr: impl Into<Expr>) -> Self {
+        let entry = (column.to_string(), expr.into());
+        match &mut self.action {
+            ConflictAction::Update { set, .. } => set.push(entry),
+            ConflictAction::Nothing => self.action = ConflictAction::Update { set: vec![entry] },
+        }
+        self
+    }
+
+    /// Specifies the `WHERE` of the `DO UPDATE` action, whichever order the action is set in.
+    /// Building fails if the final action is `DO NOTHING`.
+    pub fn update_condition(mut self, condition: impl IntoCondition) -> Self {
+        self.update_condition = Some(condition.into_condition());
+        self
+    }
+
+    /// Returns `true` if the clause names a conflict target.
+    pub(crate) fn has_target(&self) -> bool {
+        !self.target.is_empty()
+    }
+}
+
+impl Default for OnConflict {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WriteSql for OnConflict {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        if self.target.iter().any(|col| col.is_empty()) {
+            return Err(SqlBuilderError::EmptyColumnName);
+        }
+
+        if self.target.is_empty() && self.target_condition.is_some() {
+            return Err(SqlBuilderError::InvalidQuery);
+        }
+
+        w.push_str("ON CONFLICT");
+        if self.has_target() {
+            w.push_str(" (");
+            write_names(w, &self.target)?;
+            w.push(')');
+        }
+        if let Some(condition) = &self.target_condition {
+            w.push_str(" WHERE ");
+            condition.write_sql(w)?;
+        }
+
+        match &self.action {
+            ConflictAction::Nothing => {
+                if self.update_condition.is_some() {
+                    return Err(SqlBuilderError::InvalidQuery);
+                }
+                w.push_str(" DO NOTHING");
+            }
+            ConflictAction::Update { set } => {
+                if set.is_empty() {
+                    return Err(SqlBuilderError::EmptyColumnAndValue);
+                }
+
+                w.push_str(" DO UPDATE SET ");
+                for (i, (col, val)) in set.iter().enumerate() {
+                    if col.is_empty() {
+                        return Err(SqlBuilderError::EmptyColumnName);
+                    }
+                    if val.is_empty() {
+                        return Err(SqlBuilderError::EmptyValue);
+                    }
+                    if i > 0 {
+                        w.push_str(", ");
+                    }
+                    Ident::parse(col).write_sql(w)?;
+                    w.push_str(" = ");
+                    val.write_sql(w)?;
+                }
+
+                if let Some(condition) = &self.update_condition {
+                    if condition.is_empty() {
+                        return Err(SqlBuilderError::EmptyCondition);
+                    }
+                    w.push_str(" WHERE ");
+                    condition.write_sql(w)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
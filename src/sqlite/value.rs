@@ -0,0 +1,114 @@
+use super::{escape_value, BuildableStatement};
+
+/// Represents a value that is rendered as a literal or bound to a statement parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+    Bool(bool),
+}
+
+impl Value {
+    /// Returns `true` for an empty text value, which the builders reject.
+    pub(crate) fn is_empty(&self) -> bool {
+        matches!(self, Self::Text(s) if s.is_empty())
+    }
+}
+
+/// Implementation of the `BuildableStatement` trait for `Value`, rendering it as a SQL literal.
+impl BuildableStatement for Value {
+    fn build(&self) -> String {
+        match self {
+            Self::Null => "NULL".to_string(),
+            Self::Integer(i) => i.to_string(),
+            Self::Real(r) if r.is_nan() => "NULL".to_string(),
+            Self::Real(r) if r.is_infinite() => {
+                String::from(if *r > 0.0 { "1e999" } else { "-1e999" })
+            }
+            Self::Real(r) => format!("{:?}", r),
+            Self::Text(s) => escape_value(s),
+            Self::Blob(b) => {
+                let hex = b
+                    .iter()
+                    .map(|byte| format!("{:02X}", byte))
+                    .collect::<String>();
+                format!("X'{}'", hex)
+            }
+            Self::Bool(b) => String::from(if *b { "TRUE" } else { "FALSE" }),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(value: $t) -> Self {
+                    Self::Integer(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, u8, u16, u32);
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Self::Real(value.into())
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Real(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<&String> for Value {
+    fn from(value: &String) -> Self {
+        Self::Text(value.clone())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Blob(value)
+    }
+}
+
+impl From<&[u8]> for Value {
+    fn from(value: &[u8]) -> Self {
+        Self::Blob(value.to_vec())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(v) => v.into(),
+            None => Self::Null,
+        }
+    }
+}
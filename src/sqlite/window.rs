@@ -0,0 +1,307 @@
+use super::{
+    write_list, BuildableStatement, Expr, Ident, IntoColumnExpr, OrderTerm, SqlWriter, WriteSql,
+};
+use crate::errors::SqlBuilderError;
+
+/// Represents the units a window frame is measured in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameUnits {
+    Rows,
+    Range,
+    Groups,
+}
+
+/// Implementation of the `BuildableStatement` trait for `FrameUnits`, allowing it to be printed.
+impl BuildableStatement for FrameUnits {
+    fn build(&self) -> String {
+        String::from(match self {
+            Self::Rows => "ROWS",
+            Self::Range => "RANGE",
+            Self::Groups => "GROUPS",
+        })
+    }
+}
+
+/// Represents the start or end of a window frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(Expr),
+    CurrentRow,
+    Following(Expr),
+    UnboundedFollowing,
+}
+
+impl FrameBound {
+    /// Returns the position of the bound in the order SQLite requires a frame's start to
+    /// not come after its end.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::UnboundedPreceding => 0,
+            Self::Preceding(_) => 1,
+            Self::CurrentRow => 2,
+            Self::Following(_) => 3,
+            Self::UnboundedFollowing => 4,
+        }
+    }
+}
+
+impl WriteSql for FrameBound {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        match self {
+            Self::UnboundedPreceding => w.push_str("UNBOUNDED PRECEDING"),
+            Self::Preceding(expr) => {
+                expr.write_sql(w)?;
+                w.push_str(" PRECEDING");
+            }
+            Self::CurrentRow => w.push_str("CURRENT ROW"),
+            Self::Following(expr) => {
+                expr.write_sql(w)?;
+                w.push_str(" FOLLOWING");
+            }
+            Self::UnboundedFollowing => w.push_str("UNBOUNDED FOLLOWING"),
+        }
+        Ok(())
+    }
+}
+
+/// Represents the rows a window frame leaves out with `EXCLUDE`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameExclude {
+    NoOthers,
+    CurrentRow,
+    Group,
+    Ties,
+}
+
+/// Implementation of the `BuildableStatement` trait for `FrameExclude`, allowing it to be printed.
+impl BuildableStatement for FrameExclude {
+    fn build(&self) -> String {
+        String::from(match self {
+            Self::NoOthers => "NO OTHERS",
+            Self::CurrentRow => "CURRENT ROW",
+            Self::Group => "GROUP",
+            Self::Ties => "TIES",
+        })
+    }
+}
+
+/// Represents the frame specification of a window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    units: FrameUnits,
+    start: FrameBound,
+    end: Option<FrameBound>,
+    exclude: Option<FrameExclude>,
+}
+
+impl Frame {
+    /// Creates a frame from `start` to the current row, such as `ROWS 3 PRECEDING`.
+    pub fn new(units: FrameUnits, start: FrameBound) -> Self {
+        Self {
+            units,
+            start,
+            end: None,
+            exclude: None,
+        }
+    }
+
+    /// Creates a `BETWEEN start AND end` frame, which fails to build if `start` comes after
+    /// `end`, as in `BETWEEN CURRENT ROW AND 1 PRECEDING`.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Frame, FrameBound, FrameExclude, FrameUnits, Window};
+    ///
+    /// let frame = Frame::between(
+    ///     FrameUnits::Rows,
+    ///     FrameBound::UnboundedPreceding,
+    ///     FrameBound::CurrentRow,
+    /// )
+    /// .exclude(FrameExclude::Ties);
+    /// let sum = Expr::func("sum", vec![Expr::col("amount")])
+    ///     .over(Window::new().order_by([Expr::col("day")]).frame(frame));
+    ///
+    /// assert_eq!(
+    ///     sum.build().unwrap(),
+    ///     "sum(amount) OVER (ORDER BY day ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW EXCLUDE TIES)"
+    /// );
+    /// ```
+    pub fn between(units: FrameUnits, start: FrameBound, end: FrameBound) -> Self {
+        Self {
+            units,
+            start,
+            end: Some(end),
+            exclude: None,
+        }
+    }
+
+    /// Leaves rows out of the frame with `EXCLUDE`.
+    pub fn exclude(mut self, exclude: FrameExclude) -> Self {
+        self.exclude = Some(exclude);
+        self
+    }
+}
+
+impl WriteSql for Frame {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        let end = self.end.as_ref().unwrap_or(&FrameBound::CurrentRow);
+        if matches!(self.start, FrameBound::UnboundedFollowing)
+            || matches!(end, FrameBound::UnboundedPreceding)
+            || self.start.rank() > end.rank()
+        {
+            return Err(SqlBuilderError::InvalidQuery);
+        }
+
+        w.push_str(&format!("{} ", self.units.build()));
+        match &self.end {
+            None => self.start.write_sql(w)?,
+            Some(end) => {
+                w.push_str("BETWEEN ");
+                self.start.write_sql(w)?;
+                w.push_str(" AND ");
+                end.write_sql(w)?;
+            }
+        }
+
+        if let Some(exclude) = &self.exclude {
+            w.push_str(&format!(" EXCLUDE {}", exclude.build()));
+        }
+        Ok(())
+    }
+}
+
+/// Represents a window definition, used by `OVER` and by named windows of a `Select`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Window {
+    base: Option<String>,
+    partition: Vec<Expr>,
+    order: Vec<OrderTerm>,
+    frame: Option<Frame>,
+}
+
+impl Window {
+    /// Creates a new, empty `Window`, which spans every row of the result.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Expr, Window};
+    ///
+    /// let rank = Expr::func("row_number", vec![]).over(
+    ///     Window::new()
+    ///         .partition_by([Expr::col("dept")])
+    ///         .order_by([Expr::col("salary").desc()]),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     rank.build().unwrap(),
+    ///     "row_number() OVER (PARTITION BY dept ORDER BY salary DESC)"
+    /// );
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            base: None,
+            partition: Vec::new(),
+            order: Vec::new(),
+            frame: None,
+        }
+    }
+
+    /// Builds on a named window of the `Select`, which the definition extends.
+    pub fn base(mut self, name: &str) -> Self {
+        self.base = Some(name.to_string());
+        self
+    }
+
+    /// Adds expressions to the `PARTITION BY` of the window, where a string names a column.
+    pub fn partition_by<I, E>(mut self, partition: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+        E: IntoColumnExpr,
+    {
+        self.partition
+            .extend(partition.into_iter().map(IntoColumnExpr::into_column_expr));
+        self
+    }
+
+    /// Adds terms to the ordering of the rows within each partition.
+    pub fn order_by<I, T>(mut self, order: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OrderTerm>,
+    {
+        self.order.extend(order.into_iter().map(Into::into));
+        self
+    }
+
+    /// Specifies the frame of the window.
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+}
+
+impl Default for Window {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WriteSql for Window {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        let mut separator = "";
+
+        if let Some(base) = &self.base {
+            if base.is_empty() {
+                return Err(SqlBuilderError::InvalidQuery);
+            }
+            Ident::unqualified(base).write_sql(w)?;
+            separator = " ";
+        }
+
+        if !self.partition.is_empty() {
+            w.push_str(&format!("{}PARTITION BY ", separator));
+            write_list(w, &self.partition)?;
+            separator = " ";
+        }
+
+        for (i, term) in self.order.iter().enumerate() {
+            w.push_str(&match i {
+                0 => format!("{}ORDER BY ", separator),
+                _ => String::from(", "),
+            });
+            term.write_sql(w)?;
+            separator = " ";
+        }
+
+        if let Some(frame) = &self.frame {
+            w.push_str(separator);
+            frame.write_sql(w)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents the window a function is evaluated over: a definition or a named window.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Over {
+    Window(Window),
+    Named(String),
+}
+
+impl WriteSql for Over {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        match self {
+            Self::Window(window) => {
+                w.push('(');
+                window.write_sql(w)?;
+                w.push(')');
+            }
+            Self::Named(name) => {
+                if name.is_empty() {
+                    return Err(SqlBuilderError::InvalidQuery);
+                }
+                Ident::unqualified(name).write_sql(w)?;
+            }
+        }
+        Ok(())
+    }
+}
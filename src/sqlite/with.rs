@@ -0,0 +1,162 @@
+use super::{Compound, Ident, SqlWriter, WriteSql};
+use crate::errors::SqlBuilderError;
+
+/// Represents a named subquery of a `WITH` clause.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cte {
+    name: String,
+    columns: Vec<String>,
+    materialized: Option<bool>,
+    query: Compound,
+}
+
+impl Cte {
+    /// Creates a new `Cte` with the given name and query, which may be a `Select` or a `Compound`.
+    pub fn new(name: &str, query: impl Into<Compound>) -> Self {
+        Self {
+            name: name.to_string(),
+            columns: Vec::new(),
+            materialized: None,
+            query: query.into(),
+        }
+    }
+
+    /// Names the columns of the table the query produces.
+    pub fn columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.columns = columns
+            .into_iter()
+            .map(|col| col.as_ref().to_string())
+            .collect();
+        self
+    }
+
+    /// Hints that the query should be computed once, as `AS MATERIALIZED`.
+    pub fn materialized(mut self) -> Self {
+        self.materialized = Some(true);
+        self
+    }
+
+    /// Hints that the query should be inlined where it is used, as `AS NOT MATERIALIZED`.
+    pub fn not_materialized(mut self) -> Self {
+        self.materialized = Some(false);
+        self
+    }
+}
+
+impl WriteSql for Cte {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        if self.name.is_empty() {
+            return Err(SqlBuilderError::EmptyTableName);
+        }
+
+        if self.columns.iter().any(|col| col.is_empty()) {
+            return Err(SqlBuilderError::EmptyColumnName);
+        }
+
+        Ident::unqualified(&self.name).write_sql(w)?;
+        if !self.columns.is_empty() {
+            w.push_str(" (");
+            for (i, column) in self.columns.iter().enumerate() {
+                if i > 0 {
+                    w.push_str(", ");
+                }
+                Ident::unqualified(column).write_sql(w)?;
+            }
+            w.push(')');
+        }
+
+        w.push_str(match self.materialized {
+            None => " AS (",
+            Some(true) => " AS MATERIALIZED (",
+            Some(false) => " AS NOT MATERIALIZED (",
+        });
+        self.query.write_sql(w)?;
+        w.push(')');
+        Ok(())
+    }
+}
+
+/// Represents a `WITH` clause builder that prefixes a statement with common table expressions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct With {
+    recursive: bool,
+    ctes: Vec<Cte>,
+}
+
+impl With {
+    /// Creates a new, empty `With` clause.
+    /// # Example
+    /// ```
+    /// use lumus_sql_builder::sqlite::{Cte, Expr, Select, With};
+    ///
+    /// let mut adults = Select::new("users");
+    /// adults.condition(Expr::col("age").greater_than_equal(18));
+    ///
+    /// let mut select = Select::new("adults");
+    /// select
+    ///     .columns("name")
+    ///     .with(With::new().cte(Cte::new("adults", adults).materialized()));
+    ///
+    /// assert_eq!(
+    ///     select.build().unwrap(),
+    ///     "WITH adults AS MATERIALIZED (SELECT * FROM users WHERE age >= 18) SELECT name FROM adults;"
+    /// );
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            recursive: false,
+            ctes: Vec::new(),
+        }
+    }
+
+    /// Renders `WITH RECURSIVE`, allowing subqueries to refer to themselves.
+    pub fn recursive(mut self) -> Self {
+        self.recursive = true;
+        self
+    }
+
+    /// Adds a common table expression.
+    pub fn cte(mut self, cte: Cte) -> Self {
+        self.ctes.push(cte);
+        self
+    }
+}
+
+impl Default for With {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WriteSql for With {
+    fn write_sql(&self, w: &mut SqlWriter) -> Result<(), SqlBuilderError> {
+        if self.ctes.is_empty() {
+            return Err(SqlBuilderError::InvalidQuery);
+        }
+
+        w.push_str(match self.recursive {
+            true => "WITH RECURSIVE ",
+            false => "WITH ",
+        });
+        for (i, cte) in self.ctes.iter().enumerate() {
+            if i > 0 {
+                w.push_str(", ");
+            }
+            cte.write_sql(w)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a `WITH` clause followed by a space, or nothing when there is none.
+pub(crate) fn write_with(w: &mut SqlWriter, with: &Option<With>) -> Result<(), SqlBuilderError> {
+    if let Some(with) = with {
+        with.write_sql(w)?;
+        w.push(' ');
+    }
+    Ok(())
+}
@@ -1,6 +1,12 @@
+use lumus_sql_builder::errors::SqlBuilderError;
 use lumus_sql_builder::sqlite::{
-    Column, CreateTable, Delete, Insert, Join, JoinType, Select, Update, Where,
+    escape_value, BinaryOp, Case, Collation, Column, ColumnType, Compound, ConflictResolution,
+    CreateTable, Cte, Delete, Direction, Expr, ForeignKeyAction, Frame, FrameBound, FrameExclude,
+    FrameUnits, GeneratedStorage, Ident, Insert, Join, JoinType, OnConflict, OrderTerm,
+    Placeholder, Quoting, References, ResultColumn, Select, TableConstraint, Update, Value, Where,
+    Window, With,
 };
+use proptest::prelude::*;
 
 #[test]
 fn test_columns() {
@@ -25,7 +31,7 @@ fn test_columns() {
 
     let col = Column::new("is_active")
         .boolean()
-        .default("1")
+        .default(1)
         .build()
         .unwrap();
     assert_eq!(col, "is_active BOOLEAN DEFAULT 1");
@@ -38,7 +44,7 @@ fn test_columns() {
 
     let col = Column::new("created_at")
         .date()
-        .default("CURRENT_DATE")
+        .default_expr("CURRENT_DATE")
         .build()
         .unwrap();
     assert_eq!(col, "created_at DATE DEFAULT CURRENT_DATE");
@@ -98,7 +104,7 @@ fn test_create_table() {
         "orders",
         vec![
             Column::new("id").integer().primary_key().auto_increment(),
-            Column::new("total").numeric().default("0"),
+            Column::new("total").numeric().default(0),
         ],
     )
     .build()
@@ -126,7 +132,7 @@ fn test_create_table() {
         "files",
         vec![
             Column::new("file_data").blob().not_null(),
-            Column::new("is_active").boolean().default("1"),
+            Column::new("is_active").boolean().default(1),
         ],
     )
     .build()
@@ -171,20 +177,45 @@ fn test_select_queries() {
     let select = Select::new("users").columns("name, age").build().unwrap();
     assert_eq!(select, "SELECT name, age FROM users;");
 
+    let select = Select::new("users")
+        .columns(["name", "age"])
+        .build()
+        .unwrap();
+    assert_eq!(select, "SELECT name, age FROM users;");
+
+    let mut select = Select::new("users");
+    select
+        .column("u.\"first name\"")
+        .column_as("group", "team")
+        .column(Value::from("literal"));
+    assert_eq!(
+        select.build().unwrap(),
+        "SELECT u.\"first name\", \"group\" AS team, 'literal' FROM users;"
+    );
+    assert_eq!(Select::from(&select.build().unwrap()).unwrap(), select);
+    for column in ["lower(name)", "name; DROP TABLE users", "first name"] {
+        let mut select = Select::new("users");
+        select.columns([column]);
+        assert!(matches!(
+            select.build(),
+            Err(SqlBuilderError::InvalidIdentifier(_))
+        ));
+    }
+
     let mut select = Select::new("users");
     select.distinct().columns("name");
     let result = select.build().unwrap();
     assert_eq!(result, "SELECT DISTINCT name FROM users;");
 
     let select = Select::new("users")
-        .condition("age > 18".to_string())
+        .condition(Expr::raw("age > 18"))
         .build()
         .unwrap();
     assert_eq!(select, "SELECT * FROM users WHERE age > 18;");
 
     let select = Select::new("users")
         .columns("name, age")
-        .condition("age > 18".to_string())
+        .condition(Expr::raw("age > 18"))
         .build()
         .unwrap();
     assert_eq!(select, "SELECT name, age FROM users WHERE age > 18;");
@@ -203,7 +234,7 @@ fn test_select_queries() {
 
     let select = Select::new("users")
         .columns("name, age")
-        .condition("age > 18".to_string())
+        .condition(Expr::raw("age > 18"))
         .group("city")
         .order("name")
         .limit(10)
@@ -217,9 +248,13 @@ fn test_select_queries() {
 
     let select = Select::new("users u")
         .join(
-            Join::new("phones p", JoinType::Inner, "p.user_id = u.user_id")
-                .build()
-                .unwrap(),
+            Join::new(
+                "phones p",
+                JoinType::Inner,
+                Expr::col("p.user_id").equal_to(Expr::col("u.user_id")),
+            )
+            .build()
+            .unwrap(),
         )
         .build()
         .unwrap();
@@ -231,9 +266,13 @@ fn test_select_queries() {
 
     let select = Select::new("users u")
         .join(
-            Join::new("addresses a", JoinType::Left, "a.user_id = u.user_id")
-                .build()
-                .unwrap(),
+            Join::new(
+                "addresses a",
+                JoinType::Left,
+                Expr::col("a.user_id").equal_to(Expr::col("u.user_id")),
+            )
+            .build()
+            .unwrap(),
         )
         .build()
         .unwrap();
@@ -244,9 +283,13 @@ fn test_select_queries() {
 
     let select = Select::new("users u")
         .join(
-            Join::new("orders o", JoinType::Right, "o.user_id = u.user_id")
-                .build()
-                .unwrap(),
+            Join::new(
+                "orders o",
+                JoinType::Right,
+                Expr::col("o.user_id").equal_to(Expr::col("u.user_id")),
+            )
+            .build()
+            .unwrap(),
         )
         .build()
         .unwrap();
@@ -257,14 +300,22 @@ fn test_select_queries() {
 
     let select = Select::new("users u")
         .join(
-            Join::new("phones p", JoinType::Inner, "p.user_id = u.user_id")
-                .build()
-                .unwrap(),
+            Join::new(
+                "phones p",
+                JoinType::Inner,
+                Expr::col("p.user_id").equal_to(Expr::col("u.user_id")),
+            )
+            .build()
+            .unwrap(),
         )
         .join(
-            Join::new("addresses a", JoinType::Left, "a.user_id = u.user_id")
-                .build()
-                .unwrap(),
+            Join::new(
+                "addresses a",
+                JoinType::Left,
+                Expr::col("a.user_id").equal_to(Expr::col("u.user_id")),
+            )
+            .build()
+            .unwrap(),
         )
         .build()
         .unwrap();
@@ -327,7 +378,7 @@ fn test_insert_queries() {
         .unwrap();
     assert_eq!(
         insert,
-        "INSERT INTO users (name, age, city) VALUES (\'M' Carlos\', '30', 'São Paulo');"
+        "INSERT INTO users (name, age, city) VALUES ('M'' Carlos', '30', 'São Paulo');"
     );
 
     let insert = Insert::new("users")
@@ -341,278 +392,1976 @@ fn test_insert_queries() {
 }
 
 #[test]
-fn test_where_conditions() {
-    let mut condition = Where::new();
-    condition.equal_to("name", "Dayvson Spacca");
-    assert_eq!(condition.build(), "name = 'Dayvson Spacca'");
-
-    let mut condition = Where::new();
-    condition
-        .not_equal_to("age", "30")
-        .and()
-        .equal_to("status", "active");
-    assert_eq!(condition.build(), "age != '30' AND status = 'active'");
-
-    let mut condition = Where::new();
-    condition
-        .greater_than("age", "21")
-        .or()
-        .less_than("age", "18");
-    assert_eq!(condition.build(), "age > '21' OR age < '18'");
+fn test_insert_rows() {
+    let insert = Insert::new("users")
+        .columns(["name", "age"])
+        .row([Value::from("João"), Value::from(30)])
+        .row([Value::from("Maria"), Value::Null]);
+    assert_eq!(
+        insert.build().unwrap(),
+        "INSERT INTO users (name, age) VALUES ('João', 30), ('Maria', NULL);"
+    );
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
 
-    let mut condition = Where::new();
-    condition
-        .greater_than_equal("salary", "5000")
-        .and()
-        .less_than_equal("salary", "10000");
-    assert_eq!(condition.build(), "salary >= '5000' AND salary <= '10000'");
+    let insert = Insert::new("files").values(vec![("path", "a\0b")]);
+    assert_eq!(
+        insert.build().unwrap(),
+        "INSERT INTO files (path) VALUES (('a' || char(0) || 'b'));"
+    );
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
+    assert!(Insert::from("INSERT INTO files (path) VALUES (('a' || 'b'));").is_ok());
+    assert!(matches!(
+        Insert::from("INSERT INTO files (path) VALUES ((1 + 2));"),
+        Err(SqlBuilderError::Unsupported { position: 33, .. })
+    ));
+    assert!(matches!(
+        Insert::from("INSERT INTO t VALUES (1 + 2);"),
+        Err(SqlBuilderError::Unsupported { position: 22, .. })
+    ));
+
+    let insert = Insert::new("t").row([1, 2]).row([3, 4]);
+    assert_eq!(
+        insert.build().unwrap(),
+        "INSERT INTO t VALUES (1, 2), (3, 4);"
+    );
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
+    assert!(matches!(
+        Insert::new("t").row([1, 2]).row([3]).build(),
+        Err(SqlBuilderError::ColumnCountMismatch {
+            expected: 2,
+            found: 1
+        })
+    ));
+    assert!(Insert::from("INSERT INTO t VALUES (1, 2), (3);").is_err());
 
-    let mut condition = Where::new();
-    condition
-        .is_null("manager_id")
-        .and()
-        .is_not_null("department");
+    let insert = Insert::new("users")
+        .columns(["name", "age"])
+        .row([Value::from("João"), Value::from(30)])
+        .row(["Maria"]);
+    assert!(matches!(
+        insert.build(),
+        Err(SqlBuilderError::ColumnCountMismatch {
+            expected: 2,
+            found: 1
+        })
+    ));
+    assert!(Insert::new("users").columns(["name"]).build().is_err());
+    assert!(Insert::from("INSERT INTO users (name, age) VALUES ('a', 1), ('b');").is_err());
+
+    let insert = (1..=5)
+        .fold(Insert::new("t").columns(["a", "b", "c"]), |insert, i| {
+            insert.row([i, i * 10, i * 100])
+        })
+        .batch_limits(7, 500);
     assert_eq!(
-        condition.build(),
-        "manager_id IS NULL AND department IS NOT NULL"
+        insert.build_batches().unwrap(),
+        vec![
+            "INSERT INTO t (a, b, c) VALUES (1, 10, 100), (2, 20, 200);",
+            "INSERT INTO t (a, b, c) VALUES (3, 30, 300), (4, 40, 400);",
+            "INSERT INTO t (a, b, c) VALUES (5, 50, 500);",
+        ]
     );
 
-    let mut condition = Where::new();
-    condition
-        .inside("department", vec!["IT", "HR", "Finance"])
-        .and()
-        .not_inside("location", vec!["Remote", "Offsite"]);
+    let batches = insert
+        .batch_limits(1000, 3)
+        .build_batches_with_params(Placeholder::Numbered)
+        .unwrap();
+    assert_eq!(batches.len(), 2);
     assert_eq!(
-        condition.build(),
-        "department IN ('IT', 'HR', 'Finance') AND location NOT IN ('Remote', 'Offsite')"
+        batches[1],
+        (
+            "INSERT INTO t (a, b, c) VALUES (?1, ?2, ?3), (?4, ?5, ?6);".to_string(),
+            vec![4, 40, 400, 5, 50, 500]
+                .into_iter()
+                .map(Value::from)
+                .collect()
+        )
     );
+}
 
-    let mut condition = Where::new();
-    condition
-        .like("name", "%Spacca%")
-        .or()
-        .not_like("email", "%@example.com");
+#[test]
+fn test_upsert() {
+    let insert = Insert::new("users")
+        .values(vec![("email", "joao@mail.com"), ("name", "João")])
+        .on_conflict(
+            OnConflict::new()
+                .target(["email"])
+                .do_update_expr("name", Expr::excluded("name"))
+                .do_update_expr("visits", Expr::col("visits") + 1)
+                .update_condition(Expr::excluded("name").is_not(Expr::col("name"))),
+        );
     assert_eq!(
-        condition.build(),
-        "name LIKE '%Spacca%' OR email NOT LIKE '%@example.com'"
+        insert.build().unwrap(),
+        "INSERT INTO users (email, name) VALUES ('joao@mail.com', 'João') \
+         ON CONFLICT (email) DO UPDATE SET name = excluded.name, visits = visits + 1 \
+         WHERE excluded.name IS NOT name;"
+    );
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
+    assert_eq!(
+        insert.build_with_params(Placeholder::Numbered).unwrap(),
+        (
+            "INSERT INTO users (email, name) VALUES (?1, ?2) \
+             ON CONFLICT (email) DO UPDATE SET name = excluded.name, visits = visits + ?3 \
+             WHERE excluded.name IS NOT name;"
+                .to_string(),
+            vec![
+                Value::from("joao@mail.com"),
+                Value::from("João"),
+                Value::from(1)
+            ]
+        )
     );
 
-    let mut condition = Where::new();
-    condition
-        .nest()
-        .equal_to("status", "active")
-        .or()
-        .equal_to("status", "pending")
-        .unnest()
-        .and()
-        .greater_than("created_at", "2024-01-01");
+    let insert = Insert::new("users")
+        .values(vec![("email", "joao@mail.com"), ("name", "João")])
+        .on_conflict(
+            OnConflict::new()
+                .target(["email"])
+                .do_update(vec![("name", "João"), ("status", "active")])
+                .do_update_expr("seen_at", Expr::raw("CURRENT_TIMESTAMP")),
+        );
     assert_eq!(
-        condition.build(),
-        "(status = 'active' OR status = 'pending') AND created_at > '2024-01-01'"
+        insert.build().unwrap(),
+        "INSERT INTO users (email, name) VALUES ('joao@mail.com', 'João') \
+         ON CONFLICT (email) DO UPDATE SET name = 'João', status = 'active', \
+         seen_at = CURRENT_TIMESTAMP;"
     );
 
-    let mut condition = Where::from("role = 'admin'");
-    condition
-        .and()
-        .like("name", "%Spacca%")
-        .or()
-        .not_equal_to("department", "HR")
-        .and()
-        .is_null("manager_id");
+    let insert = Insert::new("users")
+        .values(vec![("email", "joao@mail.com")])
+        .on_conflict(
+            OnConflict::new()
+                .target(["email"])
+                .target_condition(Expr::col("deleted_at").is_null())
+                .do_nothing(),
+        )
+        .on_conflict(OnConflict::new());
     assert_eq!(
-        condition.build(),
-        "role = 'admin' AND name LIKE '%Spacca%' OR department != 'HR' AND manager_id IS NULL"
+        insert.build().unwrap(),
+        "INSERT INTO users (email) VALUES ('joao@mail.com') \
+         ON CONFLICT (email) WHERE deleted_at IS NULL DO NOTHING ON CONFLICT DO NOTHING;"
+    );
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
+
+    for (resolution, sql) in [
+        (ConflictResolution::Rollback, "ROLLBACK"),
+        (ConflictResolution::Abort, "ABORT"),
+        (ConflictResolution::Fail, "FAIL"),
+        (ConflictResolution::Ignore, "IGNORE"),
+        (ConflictResolution::Replace, "REPLACE"),
+    ] {
+        let insert = Insert::new("users")
+            .or(resolution)
+            .values(vec![("name", "João")]);
+        assert_eq!(
+            insert.build().unwrap(),
+            format!("INSERT OR {} INTO users (name) VALUES ('João');", sql)
+        );
+        assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
+    }
+    assert_eq!(
+        Insert::from("REPLACE INTO users (name) VALUES ('João');").unwrap(),
+        Insert::new("users")
+            .or(ConflictResolution::Replace)
+            .values(vec![("name", "João")])
     );
 
-    let mut condition = Where::new();
-    condition
-        .equal_to("name", "Dayvson Spacca")
-        .and()
-        .greater_than("age", "21")
-        .or()
-        .inside("department", vec!["IT", "HR"])
-        .and()
-        .like("email", "%@company.com")
-        .and()
-        .is_not_null("manager_id")
-        .or()
-        .not_like("role", "intern")
-        .and()
-        .nest()
-        .not_inside("location", vec!["Remote", "O'nsite"])
-        .unnest();
+    let insert = Insert::new("users").values(vec![("name", "João")]);
+    assert!(insert
+        .clone()
+        .on_conflict(OnConflict::new())
+        .on_conflict(OnConflict::new().target(["name"]))
+        .build()
+        .is_err());
+    assert!(insert
+        .clone()
+        .on_conflict(OnConflict::new().target_condition(Expr::col("active")))
+        .build()
+        .is_err());
+    assert!(insert
+        .clone()
+        .on_conflict(
+            OnConflict::new()
+                .target(["name"])
+                .do_update(Vec::<(&str, Value)>::new())
+        )
+        .build()
+        .is_err());
+
+    let on_conflict = OnConflict::new()
+        .target(["name"])
+        .update_condition(Expr::excluded("name").is_not(Expr::col("name")));
+    let upsert = insert.clone().on_conflict(
+        on_conflict
+            .clone()
+            .do_update_expr("name", Expr::excluded("name")),
+    );
     assert_eq!(
-        condition.build(),
-        "name = 'Dayvson Spacca' AND age > '21' OR department IN ('IT', 'HR') AND email LIKE '%@company.com' AND manager_id IS NOT NULL OR role NOT LIKE 'intern' AND (location NOT IN ('Remote', 'O'nsite'))"
+        upsert.build().unwrap(),
+        "INSERT INTO users (name) VALUES ('João') \
+         ON CONFLICT (name) DO UPDATE SET name = excluded.name WHERE excluded.name IS NOT name;"
     );
+    assert_eq!(Insert::from(&upsert.build().unwrap()).unwrap(), upsert);
+    assert!(matches!(
+        insert.clone().on_conflict(on_conflict).build(),
+        Err(SqlBuilderError::InvalidQuery)
+    ));
+    assert!(matches!(
+        insert
+            .on_conflict(
+                OnConflict::new()
+                    .target(["name"])
+                    .do_update(vec![("name", "Ana")])
+                    .update_condition(Where::new())
+            )
+            .build(),
+        Err(SqlBuilderError::EmptyCondition)
+    ));
 }
 
 #[test]
-fn test_update_clause() {
-    let update = Update::new("users_tb")
-        .set(vec![("name", "João")])
-        .build()
-        .unwrap();
-    assert_eq!(update, "UPDATE users_tb SET name = 'João';");
-
-    let update = Update::new("products_tb")
-        .set(vec![("price", "9.99"), ("stock", "100")])
-        .build()
-        .unwrap();
+fn test_returning() {
+    let insert = Insert::new("users")
+        .values(vec![("name", "João")])
+        .on_conflict(OnConflict::new())
+        .returning([
+            ResultColumn::from(Expr::col("id")),
+            ResultColumn::aliased(Expr::col("created_at"), "created"),
+        ]);
     assert_eq!(
-        update,
-        "UPDATE products_tb SET price = '9.99', stock = '100';"
+        insert.build().unwrap(),
+        "INSERT INTO users (name) VALUES ('João') ON CONFLICT DO NOTHING \
+         RETURNING id, created_at AS created;"
     );
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
 
-    let mut condition = Where::new();
-    condition.equal_to("age", "21");
+    let mut update = Update::new("users").set(vec![("active", false)]);
+    update
+        .condition(Expr::col("id").equal_to(7))
+        .returning([ResultColumn::All]);
+    assert_eq!(
+        update.build().unwrap(),
+        "UPDATE users SET active = FALSE WHERE id = 7 RETURNING *;"
+    );
+    assert_eq!(Update::from(&update.build().unwrap()).unwrap(), update);
+
+    let mut delete = Delete::new("users");
+    delete
+        .condition(Expr::col("id").equal_to(7))
+        .returning([ResultColumn::aliased(
+            Expr::col("name").concat(Expr::value("!")),
+            "bye",
+        )]);
+    assert_eq!(
+        delete.build_with_params(Placeholder::Numbered).unwrap(),
+        (
+            "DELETE FROM users WHERE id = ?1 RETURNING name || ?2 AS bye;".to_string(),
+            vec![Value::from(7), Value::from("!")]
+        )
+    );
+    assert_eq!(Delete::from(&delete.build().unwrap()).unwrap(), delete);
 
-    let update = Update::new("users_tb")
-        .set(vec![("name", "João")])
-        .condition(condition.build())
-        .build()
-        .unwrap();
+    let insert = Insert::new("users")
+        .values(vec![("name", "João")])
+        .returning(["id", "name"]);
+    let sql = "INSERT INTO users (name) VALUES ('João') RETURNING id, name;";
+    assert_eq!(insert.build().unwrap(), sql);
+    assert_eq!(Insert::from(sql).unwrap(), insert);
+    let mut update = Update::new("users").set(vec![("active", false)]);
+    update.returning(["id"]);
     assert_eq!(
-        update,
-        "UPDATE users_tb SET name = 'João' WHERE age = '21';"
+        update.build().unwrap(),
+        "UPDATE users SET active = FALSE RETURNING id;"
+    );
+    let mut delete = Delete::new("users");
+    delete.returning(["users.\"order\""]);
+    assert_eq!(
+        delete.build().unwrap(),
+        "DELETE FROM users RETURNING users.\"order\";"
     );
+    delete.returning(["count(*)"]);
+    assert!(matches!(
+        delete.build(),
+        Err(SqlBuilderError::InvalidIdentifier(_))
+    ));
+
+    let mut delete = Delete::new("users");
+    delete.returning([ResultColumn::aliased(Expr::col("id"), "")]);
+    assert!(delete.build().is_err());
+    assert!(Delete::from("DELETE FROM users RETURNING;").is_err());
+}
 
-    let update_result = Update::new("users_tb").build();
-    assert!(update_result.is_err());
+#[test]
+fn test_insert_sources() {
+    let mut select = Select::new("users");
+    select
+        .columns([Expr::col("name"), Expr::col("email")])
+        .condition(Expr::col("active").equal_to(true));
+    let insert = Insert::new("archive")
+        .columns(["name", "email"])
+        .select(select.clone());
     assert_eq!(
-        format!("{}", update_result.unwrap_err()),
-        "The column and the value to be inserted cannot be empty."
+        insert.build().unwrap(),
+        "INSERT INTO archive (name, email) SELECT name, email FROM users WHERE active = TRUE;"
+    );
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
+    assert_eq!(
+        insert
+            .build_batches_with_params(Placeholder::Anonymous)
+            .unwrap(),
+        vec![(
+            "INSERT INTO archive (name, email) SELECT name, email FROM users WHERE active = ?;"
+                .to_string(),
+            vec![Value::from(true)]
+        )]
     );
 
-    let update_result = Update::new("").set(vec![("name", "João")]).build();
-    assert!(update_result.is_err());
+    let insert = Insert::new("archive")
+        .select(select.clone())
+        .on_conflict(OnConflict::new());
     assert_eq!(
-        format!("{}", update_result.unwrap_err()),
-        "Table name cannot be empty."
+        insert.build().unwrap(),
+        "INSERT INTO archive SELECT name, email FROM users WHERE active = TRUE ON CONFLICT DO NOTHING;"
     );
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
+    assert!(Insert::new("archive")
+        .select(Select::new("users"))
+        .on_conflict(OnConflict::new())
+        .build()
+        .is_err());
+    assert!(Insert::new("archive")
+        .columns(["name"])
+        .row(["João"])
+        .select(select)
+        .build()
+        .is_err());
 
-    let update_result = Update::new("users_tb").set(vec![("", "João")]).build();
-    assert!(update_result.is_err());
+    let insert = Insert::new("logs")
+        .default_values()
+        .returning([ResultColumn::from(Expr::col("id"))]);
     assert_eq!(
-        format!("{}", update_result.unwrap_err()),
-        "Column name cannot be empty."
+        insert.build().unwrap(),
+        "INSERT INTO logs DEFAULT VALUES RETURNING id;"
+    );
+    assert_eq!(
+        insert.build_batches().unwrap(),
+        vec!["INSERT INTO logs DEFAULT VALUES RETURNING id;"]
     );
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
+    assert!(Insert::new("logs")
+        .columns(["name"])
+        .default_values()
+        .build()
+        .is_err());
+    assert_eq!(
+        Insert::from("INSERT INTO logs VALUES (1);").unwrap(),
+        Insert::new("logs").row([1])
+    );
+}
 
-    let update_result = Update::new("users_tb").set(vec![("name", "")]).build();
-    assert!(update_result.is_err());
+#[test]
+fn test_with() {
+    let mut active = Select::new("users");
+    active
+        .columns([Expr::col("id"), Expr::col("name")])
+        .condition(Expr::col("active").equal_to(true));
+    let mut recent = Select::new("orders");
+    recent
+        .columns([Expr::col("user_id")])
+        .condition(Expr::col("total").greater_than(100));
+    let with = With::new()
+        .cte(Cte::new("active", active).columns(["id", "name"]))
+        .cte(Cte::new("recent", recent).not_materialized());
+
+    let mut select = Select::new("active");
+    select
+        .with(with.clone())
+        .columns("name")
+        .condition(Expr::col("id").inside(vec![Expr::raw("SELECT user_id FROM recent")]));
     assert_eq!(
-        format!("{}", update_result.unwrap_err()),
-        "The value cannot be empty."
+        select.build().unwrap(),
+        "WITH active (id, name) AS (SELECT id, name FROM users WHERE active = TRUE), \
+         recent AS NOT MATERIALIZED (SELECT user_id FROM orders WHERE total > 100) \
+         SELECT name FROM active WHERE id IN (SELECT user_id FROM recent);"
+    );
+    assert_eq!(
+        select.build_with_params(Placeholder::Numbered).unwrap().1,
+        vec![Value::from(true), Value::from(100)]
     );
 
-    let mut condition = Where::new();
-    condition
-        .equal_to("age", "21")
-        .and()
-        .greater_than("score", "80");
+    let insert = Insert::new("archive")
+        .with(with.clone())
+        .select(Select::new("active"));
+    assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
 
-    let update = Update::new("users_tb")
-        .set(vec![("status", "premium")])
-        .condition(condition.build())
+    let mut update = Update::new("users").set(vec![("vip", true)]);
+    update
+        .with(with.clone())
+        .condition(Expr::col("id").inside(vec![Expr::raw("SELECT user_id FROM recent")]));
+    assert!(update
         .build()
-        .unwrap();
+        .unwrap()
+        .starts_with("WITH active (id, name) AS ("));
+
+    let mut delete = Delete::new("users");
+    delete.with(
+        With::new()
+            .recursive()
+            .cte(Cte::new("t", Select::new("users")).materialized()),
+    );
     assert_eq!(
-        update,
-        "UPDATE users_tb SET status = 'premium' WHERE age = '21' AND score > '80';"
+        delete.build().unwrap(),
+        "WITH RECURSIVE t AS MATERIALIZED (SELECT * FROM users) DELETE FROM users;"
     );
+    assert_eq!(Delete::from(&delete.build().unwrap()).unwrap(), delete);
 
-    let update = Update::new("users_tb")
-        .set(vec![("status", "active")])
-        .build()
-        .unwrap();
-    assert_eq!(update, "UPDATE users_tb SET status = 'active';");
-
-    let update = Update::new("users_tb")
-        .set(vec![("name", "O'Reilly")])
-        .build()
-        .unwrap();
-    assert_eq!(update, "UPDATE users_tb SET name = \'O'Reilly\';");
+    let mut delete = Delete::new("users");
+    delete.with(With::new());
+    assert!(delete.build().is_err());
+    delete.with(With::new().cte(Cte::new("", Select::new("users"))));
+    assert!(delete.build().is_err());
 }
 
 #[test]
-fn test_delete_clause() {
-    let delete = Delete::new("users_tb").build().unwrap();
-    assert_eq!(delete, "DELETE FROM users_tb;");
+fn test_compound() {
+    let select = |table: &str, columns: &[&str]| {
+        let mut select = Select::new(table);
+        select.columns(
+            columns
+                .iter()
+                .map(|column| Expr::col(column))
+                .collect::<Vec<_>>(),
+        );
+        select
+    };
+
+    let mut compound = Compound::new(select("users", &["id", "name"]));
+    compound
+        .union(select("admins", &["id", "name"]))
+        .union_all(select("guests", &["id", "nickname"]))
+        .intersect(Select::new("members"))
+        .except(select("banned", &["id", "name"]))
+        .order_by([Expr::col("name").desc()])
+        .limit(10)
+        .offset(20);
+    assert_eq!(
+        compound.build().unwrap(),
+        "SELECT id, name FROM users UNION SELECT id, name FROM admins \
+         UNION ALL SELECT id, nickname FROM guests INTERSECT SELECT * FROM members \
+         EXCEPT SELECT id, name FROM banned ORDER BY name DESC LIMIT 10 OFFSET 20;"
+    );
+    assert_eq!(
+        Compound::from(&compound.build().unwrap()).unwrap(),
+        compound
+    );
 
-    let mut condition = Where::new();
-    condition.equal_to("age", "21");
+    let mut compound = Compound::new(select("users", &[]));
+    compound
+        .union(select("admins", &["id", "name"]))
+        .union(select("guests", &["id"]));
+    assert!(matches!(
+        compound.build(),
+        Err(SqlBuilderError::ColumnCountMismatch {
+            expected: 2,
+            found: 1
+        })
+    ));
+
+    let mut ordered = select("users", &["id"]);
+    ordered.order("id");
+    let mut compound = Compound::new(ordered);
+    compound.union(select("admins", &["id"]));
+    assert!(compound.build().is_err());
+    assert!(Compound::from("SELECT id FROM a LIMIT 1 UNION SELECT id FROM b").is_err());
+
+    let mut single = select("users", &["id"]);
+    single.order_by([Expr::col("id")]).limit(1);
+    assert_eq!(
+        Compound::from(&single.build().unwrap()).unwrap(),
+        Compound::new(single)
+    );
 
-    let delete = Delete::new("users_tb")
-        .condition(condition.build())
-        .build()
-        .unwrap();
-    assert_eq!(delete, "DELETE FROM users_tb WHERE age = '21';");
+    let mut children = select("categories c", &["c.id", "c.parent_id"]);
+    children.join_clause(Join::new(
+        "tree t",
+        JoinType::Inner,
+        Expr::col("c.parent_id").equal_to(Expr::col("t.id")),
+    ));
+    let mut root = select("categories", &["id", "parent_id"]);
+    root.condition(Expr::col("id").equal_to(1));
+    let mut tree = Compound::new(root);
+    tree.union_all(children);
+    let mut select = Select::new("tree");
+    select.with(
+        With::new()
+            .recursive()
+            .cte(Cte::new("tree", tree).columns(["id", "parent_id"])),
+    );
+    assert_eq!(
+        select.build().unwrap(),
+        "WITH RECURSIVE tree (id, parent_id) AS (SELECT id, parent_id FROM categories WHERE id = 1 \
+         UNION ALL SELECT c.id, c.parent_id FROM categories c INNER JOIN tree t ON c.parent_id = t.id) \
+         SELECT * FROM tree;"
+    );
+    assert_eq!(Select::from(&select.build().unwrap()).unwrap(), select);
+}
 
-    let delete_result = Delete::new("").build();
-    assert!(delete_result.is_err());
+#[test]
+fn test_subqueries() {
+    let mut active = Select::new("users");
+    active
+        .columns("id, name")
+        .condition(Expr::col("active").equal_to(true));
+    let mut totals = Select::new("orders");
+    totals
+        .columns("user_id, sum(total) AS spent")
+        .condition(Expr::col("status").equal_to(Expr::value("paid")))
+        .group("user_id");
+    let mut latest = Select::new("logins l");
+    latest
+        .columns("max(l.at)")
+        .condition(Expr::col("l.user_id").equal_to(Expr::col("u.id")));
+
+    let mut select = Select::from_subquery(active, "u");
+    select
+        .columns("u.name, t.spent")
+        .column_as(Expr::subquery(latest), "last_login")
+        .join_clause(Join::subquery(
+            totals,
+            "t",
+            JoinType::Left,
+            Expr::col("t.user_id").equal_to(Expr::col("u.id")),
+        ))
+        .condition(Expr::col("t.spent").greater_than(100));
     assert_eq!(
-        format!("{}", delete_result.unwrap_err()),
-        "Table name cannot be empty."
+        select.build().unwrap(),
+        "SELECT u.name, t.spent, (SELECT max(l.at) FROM logins l WHERE l.user_id = u.id) AS last_login \
+         FROM (SELECT id, name FROM users WHERE active = TRUE) u \
+         LEFT JOIN (SELECT user_id, sum(total) AS spent FROM orders WHERE status = 'paid' GROUP BY user_id) t \
+         ON t.user_id = u.id WHERE t.spent > 100;"
+    );
+    assert_eq!(
+        select.build_with_params(Placeholder::Numbered).unwrap().1,
+        vec![Value::from(true), Value::from("paid"), Value::from(100)]
     );
+    let parsed = Select::from(&select.build().unwrap()).unwrap();
+    assert_eq!(parsed.build().unwrap(), select.build().unwrap());
+
+    let mut banned = Select::new("bans");
+    banned.columns([Expr::col("user_id")]);
+    let mut orders = Select::new("orders o");
+    orders.condition(Expr::col("o.user_id").equal_to(Expr::col("users.id")));
+    let mut average = Select::new("users");
+    average.columns([Expr::avg(Expr::col("age"))]);
 
     let mut condition = Where::new();
     condition
-        .equal_to("age", "21")
+        .not_in_subquery("id", banned.clone())
         .and()
-        .greater_than("score", "80");
+        .exists(orders.clone());
+    let mut delete = Delete::new("users");
+    delete.condition(
+        Expr::from(condition)
+            .and(Expr::col("age").greater_than(Expr::subquery(average)))
+            .and(Expr::not_exists(orders))
+            .and(Expr::col("id").in_subquery(banned)),
+    );
+    assert_eq!(
+        delete.build().unwrap(),
+        "DELETE FROM users WHERE id NOT IN (SELECT user_id FROM bans) \
+         AND EXISTS (SELECT * FROM orders o WHERE o.user_id = users.id) \
+         AND age > (SELECT avg(age) FROM users) \
+         AND NOT EXISTS (SELECT * FROM orders o WHERE o.user_id = users.id) \
+         AND id IN (SELECT user_id FROM bans);"
+    );
+    assert_eq!(Delete::from(&delete.build().unwrap()).unwrap(), delete);
 
-    let delete = Delete::new("users_tb")
-        .condition(condition.build())
+    assert!(Select::from_subquery(Select::new("users"), "")
         .build()
-        .unwrap();
+        .is_err());
+    assert!(Select::from("SELECT * FROM (SELECT * FROM users)").is_err());
+}
+
+#[test]
+fn test_having() {
+    let mut condition = Where::new();
+    condition.greater_than("total", 100);
+
+    let mut select = Select::new("orders");
+    select
+        .columns("user_id, status, sum(amount) AS total")
+        .group_by(["user_id"])
+        .group_by([Expr::col("status")])
+        .having(condition)
+        .order("total DESC");
     assert_eq!(
-        delete,
-        "DELETE FROM users_tb WHERE age = '21' AND score > '80';"
+        select.build().unwrap(),
+        "SELECT user_id, status, sum(amount) AS total FROM orders \
+         GROUP BY user_id, status HAVING total > 100 ORDER BY total DESC;"
     );
-
-    let delete_result = Delete::new("users_tb").condition("".to_string()).build();
-    assert!(delete_result.is_err());
     assert_eq!(
-        format!("{}", delete_result.unwrap_err()),
-        "The conditions cannot be empty."
+        select.build_with_params(Placeholder::Anonymous).unwrap().1,
+        vec![Value::from(100)]
     );
+    let parsed = Select::from(&select.build().unwrap()).unwrap();
+    assert_eq!(parsed.build().unwrap(), select.build().unwrap());
 
-    let delete = Delete::new("users_tb").build().unwrap();
+    let mut select = Select::new("users");
+    select.columns(["age"]).group_by(["age", "u.\"group\""]);
+    let sql = "SELECT age FROM users GROUP BY age, u.\"group\";";
+    assert_eq!(select.build().unwrap(), sql);
+    assert_eq!(Select::from(sql).unwrap(), select);
+    let mut select = Select::new("users");
+    select.group_by(["lower(name)"]);
+    assert!(matches!(
+        select.build(),
+        Err(SqlBuilderError::InvalidIdentifier(_))
+    ));
+
+    let mut select = Select::new("orders");
+    select.columns("count(*)").having(Expr::raw("count(*) > 1"));
+    assert_eq!(
+        select.build().unwrap(),
+        "SELECT count(*) FROM orders HAVING count(*) > 1;"
+    );
 
-    assert_eq!(delete, "DELETE FROM users_tb;");
+    let mut select = Select::new("orders");
+    select.having(Expr::func("max", vec![Expr::col("amount")]).greater_than(10));
+    assert!(select.build().is_ok());
+
+    let mut select = Select::new("orders");
+    select
+        .columns("user_id, max(amount, 0)")
+        .having(Expr::col("user_id").greater_than(10));
+    assert!(matches!(
+        select.build(),
+        Err(SqlBuilderError::HavingWithoutGroupBy)
+    ));
 }
 
 #[test]
-fn test_join_build() {
-    let join = Join::new("phones p", JoinType::Inner, "p.user_id = u.user_id")
-        .build()
-        .unwrap();
-    assert_eq!(join, "INNER JOIN phones p ON p.user_id = u.user_id");
-
-    let join = Join::new("addresses a", JoinType::Left, "a.user_id = u.user_id")
+fn test_window() {
+    let mut select = Select::new("sales");
+    select
+        .columns("region, day")
+        .column_as(
+            Expr::func("row_number", vec![]).over(
+                Window::new()
+                    .partition_by([Expr::col("region")])
+                    .order_by([Expr::col("amount").desc()]),
+            ),
+            "rank",
+        )
+        .column_as(
+            Expr::func("lag", vec![Expr::col("amount"), Expr::value(1)]).over_named("w"),
+            "previous",
+        )
+        .column_as(
+            Expr::func("lead", vec![Expr::col("amount")]).over(Window::new().base("w")),
+            "next",
+        )
+        .column_as(
+            Expr::func("sum", vec![Expr::col("amount")]).over(
+                Window::new().base("w").frame(
+                    Frame::between(
+                        FrameUnits::Rows,
+                        FrameBound::UnboundedPreceding,
+                        FrameBound::CurrentRow,
+                    )
+                    .exclude(FrameExclude::NoOthers),
+                ),
+            ),
+            "running",
+        )
+        .window(
+            "w",
+            Window::new().partition_by(["region"]).order_by(["day"]),
+        );
+    assert_eq!(
+        select.build().unwrap(),
+        "SELECT region, day, row_number() OVER (PARTITION BY region ORDER BY amount DESC) AS rank, \
+         lag(amount, 1) OVER w AS previous, lead(amount) OVER (w) AS next, \
+         sum(amount) OVER (w ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW EXCLUDE NO OTHERS) AS running \
+         FROM sales WINDOW w AS (PARTITION BY region ORDER BY day);"
+    );
+    let parsed = Select::from(&select.build().unwrap()).unwrap();
+    assert_eq!(parsed.build().unwrap(), select.build().unwrap());
+
+    let query = "SELECT avg(price) OVER (ORDER BY day RANGE BETWEEN 2 PRECEDING AND 2 FOLLOWING EXCLUDE TIES), \
+                 count(*) OVER (GROUPS 1 PRECEDING EXCLUDE CURRENT ROW) FROM prices;";
+    assert_eq!(Select::from(query).unwrap().build().unwrap(), query);
+
+    let mut select = Select::new("sales");
+    select.columns("sum(amount) OVER w");
+    assert!(!select.build().unwrap().contains("GROUP BY"));
+
+    let mut select = Select::new("sales");
+    select.having(
+        Expr::func("count", vec![Expr::raw("*")])
+            .over(Window::new())
+            .greater_than(1),
+    );
+    assert!(matches!(
+        select.build(),
+        Err(SqlBuilderError::HavingWithoutGroupBy)
+    ));
+
+    let invalid = [
+        Frame::new(FrameUnits::Rows, FrameBound::Following(Expr::value(1))),
+        Frame::between(
+            FrameUnits::Rows,
+            FrameBound::UnboundedFollowing,
+            FrameBound::CurrentRow,
+        ),
+        Frame::between(
+            FrameUnits::Rows,
+            FrameBound::CurrentRow,
+            FrameBound::UnboundedPreceding,
+        ),
+        Frame::between(
+            FrameUnits::Rows,
+            FrameBound::CurrentRow,
+            FrameBound::Preceding(Expr::value(1)),
+        ),
+        Frame::between(
+            FrameUnits::Range,
+            FrameBound::Following(Expr::value(1)),
+            FrameBound::CurrentRow,
+        ),
+        Frame::between(
+            FrameUnits::Groups,
+            FrameBound::Following(Expr::value(1)),
+            FrameBound::Preceding(Expr::value(1)),
+        ),
+        Frame::between(
+            FrameUnits::Rows,
+            FrameBound::UnboundedFollowing,
+            FrameBound::UnboundedFollowing,
+        ),
+    ];
+    for frame in invalid {
+        let expr = Expr::func("sum", vec![Expr::col("amount")]).over(Window::new().frame(frame));
+        assert!(matches!(expr.build(), Err(SqlBuilderError::InvalidQuery)));
+    }
+    assert!(matches!(
+        Expr::col("amount").over_named("w").build(),
+        Err(SqlBuilderError::InvalidQuery)
+    ));
+
+    let valid = [
+        Frame::between(
+            FrameUnits::Rows,
+            FrameBound::Preceding(Expr::value(2)),
+            FrameBound::Preceding(Expr::value(1)),
+        ),
+        Frame::between(
+            FrameUnits::Rows,
+            FrameBound::CurrentRow,
+            FrameBound::Following(Expr::value(1)),
+        ),
+        Frame::between(
+            FrameUnits::Rows,
+            FrameBound::Following(Expr::value(1)),
+            FrameBound::UnboundedFollowing,
+        ),
+    ];
+    for frame in valid {
+        let expr = Expr::func("sum", vec![Expr::col("amount")]).over(Window::new().frame(frame));
+        assert!(expr.build().is_ok());
+    }
+}
+
+#[test]
+fn test_functions() {
+    let mut select = Select::new("orders");
+    select
+        .columns("user_id")
+        .column_as(Expr::count_all(), "orders")
+        .column_as(Expr::sum(Expr::col("amount")), "spent")
+        .column_as(
+            Expr::round(vec![Expr::avg(Expr::col("amount")), Expr::value(2)]),
+            "average",
+        )
+        .column_as(
+            Expr::group_concat(vec![Expr::lower(Expr::col("status")), Expr::value(", ")]),
+            "statuses",
+        )
+        .column_as(
+            Expr::strftime("%Y-%m", vec![Expr::max(vec![Expr::col("created_at")])]),
+            "last_month",
+        )
+        .group_by([Expr::col("user_id")])
+        .having(Expr::count_distinct(Expr::col("product_id")).greater_than(1));
+    assert_eq!(
+        select.build().unwrap(),
+        "SELECT user_id, count(*) AS orders, sum(amount) AS spent, round(avg(amount), 2) AS average, \
+         group_concat(lower(status), ', ') AS statuses, strftime('%Y-%m', max(created_at)) AS last_month \
+         FROM orders GROUP BY user_id HAVING count(DISTINCT product_id) > 1;"
+    );
+
+    let update = Update::new("users")
+        .set_expr(
+            "name",
+            Expr::coalesce(vec![Expr::col("nickname"), Expr::col("name")]),
+        )
+        .set_expr(
+            "code",
+            Expr::substr(vec![Expr::col("code"), Expr::value(1), Expr::value(3)]),
+        )
+        .set_expr("balance", Expr::abs(Expr::ifnull(Expr::col("balance"), 0)));
+    assert_eq!(
+        update.build().unwrap(),
+        "UPDATE users SET name = coalesce(nickname, name), code = substr(code, 1, 3), \
+         balance = abs(ifnull(balance, 0));"
+    );
+
+    let age = Expr::julianday(vec![Expr::value("now")])
+        .binary(BinaryOp::Subtract, Expr::julianday(vec![Expr::col("born")]));
+    assert_eq!(age.build().unwrap(), "julianday('now') - julianday(born)");
+
+    let math = [
+        (Expr::ceil(Expr::col("a")), "ceil(a)"),
+        (Expr::floor(Expr::col("a") / 2), "floor(a / 2)"),
+        (Expr::trunc(Expr::col("a")), "trunc(a)"),
+        (Expr::sign(Expr::col("a")), "sign(a)"),
+        (Expr::sqrt(Expr::col("a")), "sqrt(a)"),
+        (Expr::pow(Expr::col("a"), 3), "pow(a, 3)"),
+        (Expr::exp(Expr::col("a")), "exp(a)"),
+        (Expr::ln(Expr::col("a")), "ln(a)"),
+        (Expr::log(Expr::col("a")), "log(a)"),
+        (Expr::log_base(2, Expr::col("a")), "log(2, a)"),
+        (Expr::log2(Expr::col("a")), "log2(a)"),
+        (Expr::modulo(Expr::col("a"), 2.5), "mod(a, 2.5)"),
+        (Expr::pi() * Expr::col("r"), "pi() * r"),
+    ];
+    for (expr, sql) in math {
+        assert_eq!(expr.build().unwrap(), sql);
+    }
+
+    let invalid = [
+        Expr::func("sqrt", vec![]),
+        Expr::func("pow", vec![Expr::col("a")]),
+        Expr::func("log", vec![Expr::col("a"), Expr::col("b"), Expr::col("c")]),
+        Expr::func("PI", vec![Expr::value(1)]),
+        Expr::func("atan2", vec![Expr::col("a")]),
+        Expr::coalesce(vec![Expr::col("a")]),
+        Expr::substr(vec![Expr::col("a")]),
+        Expr::round(vec![Expr::col("a"), Expr::value(1), Expr::value(2)]),
+        Expr::func("LOWER", vec![Expr::col("a"), Expr::col("b")]),
+        Expr::lag(vec![]),
+    ];
+    for expr in invalid {
+        assert!(matches!(
+            expr.build(),
+            Err(SqlBuilderError::InvalidArgumentCount { .. })
+        ));
+    }
+    assert_eq!(
+        Expr::coalesce(vec![]).build().unwrap_err().to_string(),
+        "The function coalesce does not accept 0 arguments."
+    );
+    assert!(Expr::func("my_func", vec![]).build().is_ok());
+}
+
+#[test]
+fn test_select_columns() {
+    let mut select = Select::new("users");
+    select
+        .all_from("users")
+        .column(Expr::col("orders.id"))
+        .column_as(Expr::count(Expr::col("orders.id")), "order count")
+        .columns([
+            Expr::col("select"),
+            Expr::col("São"),
+            Expr::col("main.\"odd name\""),
+        ])
+        .columns(vec![ResultColumn::aliased(Expr::col("a\"b"), "c")])
+        .join_clause(Join::new(
+            "orders",
+            JoinType::Left,
+            Expr::col("orders.user_id").equal_to(Expr::col("users.id")),
+        ))
+        .group_by([Expr::col("users.id")]);
+    assert_eq!(
+        select.build().unwrap(),
+        "SELECT users.*, orders.id, count(orders.id) AS \"order count\", \"select\", São, \
+         main.\"odd name\", \"a\"\"b\" AS c FROM users \
+         LEFT JOIN orders ON orders.user_id = users.id GROUP BY users.id;"
+    );
+    let parsed = Select::from(&select.build().unwrap()).unwrap();
+    assert_eq!(parsed.build().unwrap(), select.build().unwrap());
+
+    let mut select = Select::new("users");
+    select.columns("id, name").column(Expr::col("age"));
+    assert_eq!(select.build().unwrap(), "SELECT id, name, age FROM users;");
+
+    let mut select = Select::new("users");
+    select.column(Expr::col("users."));
+    assert!(matches!(
+        select.build(),
+        Err(SqlBuilderError::EmptyColumnName)
+    ));
+
+    let mut select = Select::new("users");
+    select.column_as(Expr::col("id"), "");
+    assert!(matches!(
+        select.build(),
+        Err(SqlBuilderError::EmptyColumnName)
+    ));
+
+    let mut select = Select::new("users");
+    select.all_from("");
+    assert!(matches!(
+        select.build(),
+        Err(SqlBuilderError::EmptyTableName)
+    ));
+
+    let mut users = Select::new("users");
+    users.all_from("users");
+    let mut compound = Compound::new(users);
+    compound.union(Select::new("admins"));
+    assert!(compound.build().is_ok());
+}
+
+#[test]
+fn test_order_terms() {
+    let mut select = Select::new("users");
+    select
+        .order_by([OrderTerm::new(Expr::col("name")).collate("NOCASE").asc()])
+        .order_by([
+            Expr::col("last_seen").desc().nulls_last(),
+            OrderTerm::new(Expr::col("id")).nulls_first(),
+        ]);
+    let sql = "SELECT * FROM users ORDER BY name COLLATE NOCASE ASC, last_seen DESC NULLS LAST, id NULLS FIRST;";
+    assert_eq!(select.build().unwrap(), sql);
+    assert_eq!(Select::from(sql).unwrap(), select);
+
+    select.order("id");
+    assert_eq!(select.build().unwrap(), "SELECT * FROM users ORDER BY id;");
+
+    let mut select = Select::new("users");
+    select
+        .order_by(["name", "u.\"order\""])
+        .order_by([OrderTerm::new("age").desc(), 2.into()]);
+    let sql = "SELECT * FROM users ORDER BY name, u.\"order\", age DESC, 2;";
+    assert_eq!(select.build().unwrap(), sql);
+    assert_eq!(Select::from(sql).unwrap(), select);
+    let mut select = Select::new("users");
+    select.order_by(["name DESC"]);
+    assert!(matches!(
+        select.build(),
+        Err(SqlBuilderError::InvalidIdentifier(_))
+    ));
+
+    let mut compound = Compound::new(Select::new("users"));
+    compound
+        .union(Select::new("admins"))
+        .order_by([Expr::col("name").desc()]);
+    assert_eq!(
+        compound.build().unwrap(),
+        "SELECT * FROM users UNION SELECT * FROM admins ORDER BY name DESC;"
+    );
+
+    let rank = Expr::rank().over(Window::new().order_by([Expr::col("score").desc()]));
+    assert_eq!(rank.build().unwrap(), "rank() OVER (ORDER BY score DESC)");
+
+    let mut select = Select::new("users");
+    select.order_by([OrderTerm::new(Expr::raw(" "))]);
+    assert!(matches!(
+        select.build(),
+        Err(SqlBuilderError::EmptyColumnName)
+    ));
+
+    let mut delete = Delete::new("logs");
+    delete
+        .condition(Expr::col("level").equal_to(Expr::value("debug")))
+        .order_by([Expr::col("created_at").asc()])
+        .limit(100);
+    let sql = "DELETE FROM logs WHERE level = 'debug' ORDER BY created_at ASC LIMIT 100;";
+    if cfg!(feature = "update-delete-limit") {
+        assert_eq!(delete.build().unwrap(), sql);
+        assert_eq!(Delete::from(sql).unwrap(), delete);
+        let mut delete = Delete::new("logs");
+        delete.order("created_at").offset(10);
+        assert!(matches!(delete.build(), Err(SqlBuilderError::InvalidQuery)));
+    } else {
+        assert!(matches!(
+            delete.build(),
+            Err(SqlBuilderError::UpdateDeleteLimitDisabled)
+        ));
+    }
+}
+
+#[test]
+fn test_identifiers() {
+    let mut condition = Where::new();
+    condition.equal_to("group", "admin");
+
+    let mut select = Select::new("main.order AS o");
+    select
+        .column(Expr::col("o.key"))
+        .column_as(Expr::col("user name"), "select")
+        .condition(condition)
+        .order_by([Expr::col("o.desc")]);
+    let sql = "SELECT o.\"key\", \"user name\" AS \"select\" FROM main.\"order\" AS o \
+               WHERE \"group\" = 'admin' ORDER BY o.\"desc\";";
+    assert_eq!(select.build().unwrap(), sql);
+    assert_eq!(Select::from(sql).unwrap(), select);
+
+    let table = CreateTable::new(
+        "user data",
+        vec![
+            Column::new("order").integer().primary_key(),
+            Column::new("a\"b").text(),
+        ],
+    );
+    assert_eq!(
+        table.build().unwrap(),
+        "CREATE TABLE \"user data\" (\"order\" INTEGER PRIMARY KEY, \"a\"\"b\" TEXT);"
+    );
+
+    let insert = Insert::new("main.settings")
+        .columns(["key", "value"])
+        .row([Value::from("theme"), Value::from("dark")])
+        .on_conflict(
+            OnConflict::new()
+                .target(["key"])
+                .do_update_expr("value", Expr::excluded("value")),
+        );
+    let sql = "INSERT INTO main.settings (\"key\", value) VALUES ('theme', 'dark') \
+               ON CONFLICT (\"key\") DO UPDATE SET value = excluded.value;";
+    assert_eq!(insert.build().unwrap(), sql);
+    assert_eq!(Insert::from(sql).unwrap().build().unwrap(), sql);
+
+    let update = Update::new("table").set(vec![("check", true)]);
+    assert_eq!(
+        update.build().unwrap(),
+        "UPDATE \"table\" SET \"check\" = TRUE;"
+    );
+
+    let column = Expr::from(Ident::qualified("main", "users").quoting(Quoting::Always));
+    assert_eq!(column.build().unwrap(), "\"main\".\"users\"");
+    let column = Expr::from(Ident::new("order").quoting(Quoting::Never));
+    assert_eq!(column.build().unwrap(), "order");
+    assert!(matches!(
+        Ident::new("users; --").quoting(Quoting::Never).build(),
+        Err(SqlBuilderError::InvalidIdentifier(_))
+    ));
+    assert_eq!(
+        Ident::parse("[my table].`col`").build().unwrap(),
+        "\"my table\".col"
+    );
+    assert!(matches!(
+        Ident::parse("users.").build(),
+        Err(SqlBuilderError::EmptyColumnName)
+    ));
+
+    assert!(matches!(
+        Delete::new("users; DROP TABLE users").build(),
+        Err(SqlBuilderError::InvalidIdentifier(_))
+    ));
+    assert_eq!(
+        Delete::new("users;").build().unwrap(),
+        "DELETE FROM \"users;\";"
+    );
+
+    let mut condition = Where::new();
+    condition
+        .equal_to("u.\"first name\"", "Ana")
+        .and()
+        .equal_to("lower(name)", "x");
+    assert!(matches!(
+        condition.build(),
+        Err(SqlBuilderError::InvalidIdentifier(field)) if field == "lower(name)"
+    ));
+    let mut select = Select::new("users");
+    select.condition(condition);
+    assert!(select.build().is_err());
+    let mut condition = Where::new();
+    condition
+        .equal_to("u.\"first name\"", "Ana")
+        .and()
+        .expr(Expr::raw("lower(name)").equal_to("x"));
+    assert_eq!(
+        condition.build().unwrap(),
+        "u.\"first name\" = 'Ana' AND (lower(name)) = 'x'"
+    );
+
+    let mut select = Select::new("users");
+    select
+        .columns([Expr::col("name")])
+        .condition(Expr::col("id").in_subquery(Select::new("admins")))
+        .quoting(Quoting::Always);
+    assert_eq!(
+        select.build().unwrap(),
+        "SELECT \"name\" FROM \"users\" WHERE \"id\" IN (SELECT * FROM \"admins\");"
+    );
+    let mut admins = Select::new("admins");
+    admins.quoting(Quoting::WhenNeeded);
+    let mut select = Select::new("users");
+    select
+        .condition(Expr::col("id").in_subquery(admins))
+        .quoting(Quoting::Always);
+    assert_eq!(
+        select.build().unwrap(),
+        "SELECT * FROM \"users\" WHERE \"id\" IN (SELECT * FROM admins);"
+    );
+    let mut delete = Delete::new("users");
+    delete
+        .condition(Expr::col("first name").equal_to("Ana"))
+        .quoting(Quoting::Never);
+    assert!(matches!(
+        delete.build(),
+        Err(SqlBuilderError::InvalidIdentifier(_))
+    ));
+    let insert = Insert::new("users")
+        .values(vec![("name", "Ana")])
+        .quoting(Quoting::Always);
+    assert_eq!(
+        insert.build().unwrap(),
+        "INSERT INTO \"users\" (\"name\") VALUES ('Ana');"
+    );
+    let create_table = CreateTable::new("users", vec![Column::new("code").integer()])
+        .constraint(TableConstraint::unique(["code"]))
+        .quoting(Quoting::Never);
+    assert_eq!(
+        create_table.build().unwrap(),
+        "CREATE TABLE users (code INTEGER, UNIQUE (code));"
+    );
+}
+
+#[test]
+fn test_table_constraints() {
+    let table = CreateTable::new(
+        "enrollments",
+        vec![
+            Column::new("student_id").integer().not_null(),
+            Column::new("course_id").integer().not_null(),
+            Column::new("grade").integer(),
+        ],
+    )
+    .constraint(TableConstraint::primary_key(["student_id", "course_id"]).name("pk_enrollments"))
+    .constraint(TableConstraint::unique(["student_id", "grade"]))
+    .constraint(TableConstraint::check(
+        Expr::col("grade").between(Expr::value(0), Expr::value(100)),
+    ))
+    .constraint(
+        TableConstraint::foreign_key(["course_id"], References::new("courses", ["id"]))
+            .name("fk_course"),
+    )
+    .constraint(TableConstraint::foreign_key(
+        ["student_id"],
+        References::new("students", Vec::<&str>::new()),
+    ));
+    let sql = "CREATE TABLE enrollments (student_id INTEGER NOT NULL, course_id INTEGER NOT NULL, \
+               grade INTEGER, CONSTRAINT pk_enrollments PRIMARY KEY (student_id, course_id), \
+               UNIQUE (student_id, grade), CHECK (grade BETWEEN 0 AND 100), \
+               CONSTRAINT fk_course FOREIGN KEY (course_id) REFERENCES courses (id), \
+               FOREIGN KEY (student_id) REFERENCES students);";
+    assert_eq!(table.build().unwrap(), sql);
+    assert_eq!(CreateTable::from(sql).unwrap(), table);
+
+    let quoted = TableConstraint::unique(["order", "key"]).name("unique order");
+    assert_eq!(
+        quoted.build().unwrap(),
+        "CONSTRAINT \"unique order\" UNIQUE (\"order\", \"key\")"
+    );
+
+    assert!(matches!(
+        TableConstraint::primary_key(Vec::<&str>::new()).build(),
+        Err(SqlBuilderError::NoColumnsSpecified)
+    ));
+    assert!(matches!(
+        TableConstraint::unique([""]).build(),
+        Err(SqlBuilderError::EmptyColumnName)
+    ));
+    assert!(matches!(
+        TableConstraint::foreign_key(["a", "b"], References::new("t", ["x"])).build(),
+        Err(SqlBuilderError::ColumnCountMismatch {
+            expected: 2,
+            found: 1
+        })
+    ));
+    assert!(matches!(
+        TableConstraint::foreign_key(["a"], References::new("", ["x"])).build(),
+        Err(SqlBuilderError::EmptyTableName)
+    ));
+    assert!(matches!(
+        CreateTable::new("t", vec![Column::new("id").integer().primary_key()])
+            .constraint(TableConstraint::primary_key(["id"]))
+            .build(),
+        Err(SqlBuilderError::InvalidQuery)
+    ));
+    assert!(CreateTable::from("CREATE TABLE t (id INTEGER, CHECK (id > 0), name TEXT);").is_err());
+}
+
+#[test]
+fn test_column_references() {
+    let table = CreateTable::new(
+        "posts",
+        vec![
+            Column::new("id").integer().primary_key(),
+            Column::new("author_id")
+                .integer()
+                .not_null()
+                .references("users", "id")
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::NoAction),
+            Column::new("editor_id")
+                .integer()
+                .references("users", "id")
+                .on_delete(ForeignKeyAction::SetNull)
+                .deferred(),
+            Column::new("category_id")
+                .integer()
+                .default(0)
+                .references("categories", "id")
+                .on_delete(ForeignKeyAction::SetDefault)
+                .on_update(ForeignKeyAction::Restrict),
+        ],
+    );
+    let sql = "CREATE TABLE posts (id INTEGER PRIMARY KEY, \
+               author_id INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE ON UPDATE NO ACTION, \
+               editor_id INTEGER REFERENCES users (id) ON DELETE SET NULL DEFERRABLE INITIALLY DEFERRED, \
+               category_id INTEGER DEFAULT 0 REFERENCES categories (id) ON DELETE SET DEFAULT ON UPDATE RESTRICT);";
+    assert_eq!(table.build().unwrap(), sql);
+    assert_eq!(CreateTable::from(sql).unwrap(), table);
+
+    let constraint = TableConstraint::foreign_key(
+        ["owner_id"],
+        References::new("users", Vec::<&str>::new())
+            .on_update(ForeignKeyAction::Cascade)
+            .deferred(),
+    );
+    assert_eq!(
+        constraint.build().unwrap(),
+        "FOREIGN KEY (owner_id) REFERENCES users ON UPDATE CASCADE DEFERRABLE INITIALLY DEFERRED"
+    );
+    assert_eq!(
+        CreateTable::from("CREATE TABLE pets (owner_id INTEGER REFERENCES users);")
+            .unwrap()
+            .build()
+            .unwrap(),
+        "CREATE TABLE pets (owner_id INTEGER REFERENCES users);"
+    );
+
+    assert!(matches!(
+        Column::new("user_id")
+            .integer()
+            .on_delete(ForeignKeyAction::Cascade)
+            .build(),
+        Err(SqlBuilderError::EmptyTableName)
+    ));
+    assert!(matches!(
+        Column::new("user_id")
+            .integer()
+            .references("users", "")
+            .build(),
+        Err(SqlBuilderError::EmptyColumnName)
+    ));
+    assert!(
+        CreateTable::from("CREATE TABLE t (a INTEGER REFERENCES b (id) ON DELETE NOTHING);")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_column_constraints() {
+    let table = CreateTable::new(
+        "accounts",
+        vec![
+            Column::new("id")
+                .integer()
+                .primary_key_ordered(Direction::Desc)
+                .on_conflict(ConflictResolution::Rollback),
+            Column::new("email")
+                .text()
+                .not_null()
+                .on_conflict(ConflictResolution::Fail)
+                .unique()
+                .on_conflict(ConflictResolution::Replace)
+                .collate(Collation::NoCase),
+            Column::new("code")
+                .text()
+                .collate(Collation::RTrim)
+                .check(Expr::length(Expr::col("code")).equal_to(Expr::value(3))),
+            Column::new("balance")
+                .integer()
+                .default(0)
+                .check(Expr::col("balance").greater_than_equal(Expr::value(0))),
+            Column::new("name")
+                .text()
+                .collate(Collation::Custom("my order".to_string())),
+        ],
+    );
+    let sql = "CREATE TABLE accounts (id INTEGER PRIMARY KEY DESC ON CONFLICT ROLLBACK, \
+               email TEXT NOT NULL ON CONFLICT FAIL UNIQUE ON CONFLICT REPLACE COLLATE NOCASE, \
+               code TEXT COLLATE RTRIM CHECK (length(code) = 3), \
+               balance INTEGER DEFAULT 0 CHECK (balance >= 0), name TEXT COLLATE \"my order\");";
+    assert_eq!(table.build().unwrap(), sql);
+    assert_eq!(CreateTable::from(sql).unwrap().build().unwrap(), sql);
+
+    let column = Column::new("id")
+        .integer()
+        .primary_key_ordered(Direction::Asc)
+        .auto_increment()
+        .collate(Collation::Binary);
+    assert_eq!(
+        column.build().unwrap(),
+        "id INTEGER PRIMARY KEY ASC AUTOINCREMENT COLLATE BINARY"
+    );
+    assert_eq!(Collation::from_name("nocase"), Collation::NoCase);
+
+    assert!(matches!(
+        Column::new("id")
+            .integer()
+            .on_conflict(ConflictResolution::Ignore)
+            .build(),
+        Err(SqlBuilderError::InvalidQuery)
+    ));
+    assert!(matches!(
+        Column::new("id").integer().check(Expr::raw("")).build(),
+        Err(SqlBuilderError::EmptyCondition)
+    ));
+    assert!(matches!(
+        Column::new("id")
+            .text()
+            .collate(Collation::Custom(String::new()))
+            .build(),
+        Err(SqlBuilderError::InvalidQuery)
+    ));
+    assert!(
+        CreateTable::from("CREATE TABLE t (id INTEGER NOT NULL ON CONFLICT NOTHING);").is_err()
+    );
+}
+
+#[test]
+fn test_generated_columns() {
+    let table = CreateTable::new(
+        "people",
+        vec![
+            Column::new("id").integer().primary_key(),
+            Column::new("first_name").text().not_null(),
+            Column::new("last_name").text().not_null(),
+            Column::new("full_name").text().generated(
+                Expr::col("first_name")
+                    .binary(BinaryOp::Concat, Expr::value(" "))
+                    .binary(BinaryOp::Concat, Expr::col("last_name")),
+                GeneratedStorage::Virtual,
+            ),
+            Column::new("price_cents").integer(),
+            Column::new("quantity").integer(),
+            Column::new("total_cents").integer().not_null().generated(
+                Expr::col("price_cents") * Expr::col("quantity"),
+                GeneratedStorage::Stored,
+            ),
+        ],
+    );
+    let sql = "CREATE TABLE people (id INTEGER PRIMARY KEY, first_name TEXT NOT NULL, \
+               last_name TEXT NOT NULL, \
+               full_name TEXT GENERATED ALWAYS AS (first_name || ' ' || last_name) VIRTUAL, \
+               price_cents INTEGER, quantity INTEGER, \
+               total_cents INTEGER NOT NULL GENERATED ALWAYS AS (price_cents * quantity) STORED);";
+    assert_eq!(table.build().unwrap(), sql);
+    assert_eq!(CreateTable::from(sql).unwrap(), table);
+    assert_eq!(
+        CreateTable::from("CREATE TABLE t (a INTEGER, b INTEGER AS (a + 1));")
+            .unwrap()
+            .build()
+            .unwrap(),
+        "CREATE TABLE t (a INTEGER, b INTEGER GENERATED ALWAYS AS (a + 1) VIRTUAL);"
+    );
+
+    assert!(matches!(
+        Column::new("total")
+            .integer()
+            .default(0)
+            .generated(Expr::col("a") + 1, GeneratedStorage::Stored)
+            .build(),
+        Err(SqlBuilderError::InvalidQuery)
+    ));
+    assert!(matches!(
+        Column::new("total")
+            .integer()
+            .generated(Expr::col("a") + 1, GeneratedStorage::Virtual)
+            .default_expr("CURRENT_TIMESTAMP")
+            .build(),
+        Err(SqlBuilderError::InvalidQuery)
+    ));
+    assert!(matches!(
+        Column::new("total")
+            .integer()
+            .primary_key()
+            .generated(Expr::col("a") + 1, GeneratedStorage::Stored)
+            .build(),
+        Err(SqlBuilderError::InvalidQuery)
+    ));
+    assert!(matches!(
+        Column::new("total")
+            .integer()
+            .generated(Expr::raw(""), GeneratedStorage::Stored)
+            .build(),
+        Err(SqlBuilderError::EmptyValue)
+    ));
+}
+
+#[test]
+fn test_table_options() {
+    let table = CreateTable::new(
+        "events",
+        vec![
+            Column::new("id").integer().primary_key(),
+            Column::new("kind").text().not_null(),
+            Column::new("score").real(),
+            Column::new("payload").blob(),
+            Column::new("extra").any(),
+        ],
+    )
+    .strict();
+    let sql = "CREATE TABLE events (id INTEGER PRIMARY KEY, kind TEXT NOT NULL, score REAL, \
+               payload BLOB, extra ANY) STRICT;";
+    assert_eq!(table.build().unwrap(), sql);
+    assert_eq!(CreateTable::from(sql).unwrap(), table);
+
+    let table = CreateTable::new(
+        "tags",
+        vec![
+            Column::new("name").text().primary_key(),
+            Column::new("count").integer(),
+        ],
+    )
+    .without_rowid();
+    let sql = "CREATE TABLE tags (name TEXT PRIMARY KEY, count INTEGER) WITHOUT ROWID;";
+    assert_eq!(table.build().unwrap(), sql);
+    assert_eq!(CreateTable::from(sql).unwrap(), table);
+
+    let table = CreateTable::new(
+        "scratch",
+        vec![Column::new("a").integer(), Column::new("b").text()],
+    )
+    .constraint(TableConstraint::primary_key(["a", "b"]))
+    .temporary()
+    .if_not_exists()
+    .strict()
+    .without_rowid();
+    let sql = "CREATE TEMP TABLE IF NOT EXISTS scratch (a INTEGER, b TEXT, PRIMARY KEY (a, b)) \
+               STRICT, WITHOUT ROWID;";
+    assert_eq!(table.build().unwrap(), sql);
+    assert_eq!(CreateTable::from(sql).unwrap(), table);
+    assert_eq!(
+        CreateTable::from(
+            "CREATE TEMPORARY TABLE scratch (a INTEGER, b TEXT, PRIMARY KEY (a, b)) \
+             WITHOUT ROWID, STRICT;"
+        )
+        .unwrap()
+        .if_not_exists(),
+        table
+    );
+
+    for column in [
+        Column::new("flag").boolean(),
+        Column::new("day").date(),
+        Column::new("at").datetime(),
+        Column::new("amount").numeric(),
+    ] {
+        assert!(matches!(
+            CreateTable::new("t", vec![Column::new("id").integer(), column])
+                .strict()
+                .build(),
+            Err(SqlBuilderError::InvalidColumnType)
+        ));
+    }
+    assert!(CreateTable::new("t", vec![Column::new("flag").boolean()])
+        .build()
+        .is_ok());
+
+    let table = CreateTable::new(
+        "t",
+        vec![
+            Column::new("id").int().primary_key(),
+            Column::new("code").custom_type("varchar(8)"),
+        ],
+    );
+    let sql = "CREATE TABLE t (id INT PRIMARY KEY, code VARCHAR(8));";
+    assert_eq!(table.build().unwrap(), sql);
+    assert_eq!(
+        CreateTable::from(sql).unwrap(),
+        CreateTable::new(
+            "t",
+            vec![
+                Column::new("id").int().primary_key(),
+                Column::new("code").custom_type("VARCHAR(8)"),
+            ],
+        )
+    );
+    assert!(CreateTable::new("t", vec![Column::new("id").int()])
+        .strict()
+        .build()
+        .is_ok());
+    assert!(matches!(
+        table.strict().build(),
+        Err(SqlBuilderError::InvalidColumnType)
+    ));
+    let table = CreateTable::new(
+        "t",
+        vec![
+            Column::new("id").custom_type("integer").primary_key(),
+            Column::new("name").custom_type("TEXT"),
+            Column::new("data").custom_type("Any"),
+        ],
+    )
+    .strict();
+    assert_eq!(
+        table.build().unwrap(),
+        "CREATE TABLE t (id INTEGER PRIMARY KEY, name TEXT, data ANY) STRICT;"
+    );
+    assert_eq!(ColumnType::from_name("integer"), ColumnType::Integer);
+    assert_eq!(
+        ColumnType::from_name("VARCHAR(8)"),
+        ColumnType::Custom(String::from("VARCHAR(8)"))
+    );
+    assert_eq!(
+        CreateTable::from("CREATE TABLE t (price DECIMAL(10, 2) NOT NULL);")
+            .unwrap()
+            .build()
+            .unwrap(),
+        "CREATE TABLE t (price DECIMAL(10, 2) NOT NULL);"
+    );
+    for type_name in [
+        "",
+        "VARCHAR(",
+        "TEXT)",
+        "NOT NULL",
+        "VARCHAR(x)",
+        "TEXT; DROP TABLE t",
+    ] {
+        assert!(matches!(
+            Column::new("code").custom_type(type_name).build(),
+            Err(SqlBuilderError::InvalidColumnType)
+        ));
+    }
+    assert_eq!(
+        CreateTable::from("CREATE TABLE t (id PRIMARY KEY);").unwrap(),
+        CreateTable::new("t", vec![Column::new("id").untyped().primary_key()])
+    );
+    assert!(matches!(
+        CreateTable::new("t", vec![Column::new("id").untyped()])
+            .strict()
+            .build(),
+        Err(SqlBuilderError::InvalidColumnType)
+    ));
+    assert!(matches!(
+        CreateTable::new("t", vec![Column::new("id").integer()])
+            .without_rowid()
+            .build(),
+        Err(SqlBuilderError::InvalidQuery)
+    ));
+    assert!(CreateTable::from("CREATE TABLE t (id INTEGER PRIMARY KEY) WITHOUT ROWS;").is_err());
+}
+
+#[test]
+fn test_where_conditions() {
+    let mut condition = Where::new();
+    condition.equal_to("name", "Dayvson Spacca");
+    assert_eq!(condition.build().unwrap(), "name = 'Dayvson Spacca'");
+
+    let mut condition = Where::new();
+    condition
+        .not_equal_to("age", "30")
+        .and()
+        .equal_to("status", "active");
+    assert_eq!(
+        condition.build().unwrap(),
+        "age != '30' AND status = 'active'"
+    );
+
+    let mut condition = Where::new();
+    condition
+        .greater_than("age", "21")
+        .or()
+        .less_than("age", "18");
+    assert_eq!(condition.build().unwrap(), "age > '21' OR age < '18'");
+
+    let mut condition = Where::new();
+    condition
+        .greater_than_equal("salary", "5000")
+        .and()
+        .less_than_equal("salary", "10000");
+    assert_eq!(
+        condition.build().unwrap(),
+        "salary >= '5000' AND salary <= '10000'"
+    );
+
+    let mut condition = Where::new();
+    condition
+        .is_null("manager_id")
+        .and()
+        .is_not_null("department");
+    assert_eq!(
+        condition.build().unwrap(),
+        "manager_id IS NULL AND department IS NOT NULL"
+    );
+
+    let mut condition = Where::new();
+    condition
+        .inside("department", vec!["IT", "HR", "Finance"])
+        .and()
+        .not_inside("location", vec!["Remote", "Offsite"]);
+    assert_eq!(
+        condition.build().unwrap(),
+        "department IN ('IT', 'HR', 'Finance') AND location NOT IN ('Remote', 'Offsite')"
+    );
+
+    let mut condition = Where::new();
+    condition
+        .like("name", "%Spacca%")
+        .or()
+        .not_like("email", "%@example.com");
+    assert_eq!(
+        condition.build().unwrap(),
+        "name LIKE '%Spacca%' OR email NOT LIKE '%@example.com'"
+    );
+
+    let mut condition = Where::new();
+    condition
+        .nest()
+        .equal_to("status", "active")
+        .or()
+        .equal_to("status", "pending")
+        .unnest()
+        .and()
+        .greater_than("created_at", "2024-01-01");
+    assert_eq!(
+        condition.build().unwrap(),
+        "(status = 'active' OR status = 'pending') AND created_at > '2024-01-01'"
+    );
+
+    let mut condition = Where::from("role = 'admin'");
+    condition
+        .and()
+        .like("name", "%Spacca%")
+        .or()
+        .not_equal_to("department", "HR")
+        .and()
+        .is_null("manager_id");
+    assert_eq!(
+        condition.build().unwrap(),
+        "(role = 'admin') AND name LIKE '%Spacca%' OR department != 'HR' AND manager_id IS NULL"
+    );
+
+    let mut condition = Where::from("a = 1 OR b = 2");
+    condition.and().equal_to("c", 3);
+    assert_eq!(condition.build().unwrap(), "(a = 1 OR b = 2) AND c = 3");
+    assert_eq!(
+        Expr::raw("a OR b").and(Expr::col("c")).build().unwrap(),
+        "(a OR b) AND c"
+    );
+    assert_eq!(
+        Expr::col("c").and(Expr::raw("a OR b")).build().unwrap(),
+        "c AND (a OR b)"
+    );
+    assert_eq!(Where::from("a OR b").build().unwrap(), "a OR b");
+
+    let mut condition = Where::new();
+    condition
+        .equal_to("name", "Dayvson Spacca")
+        .and()
+        .greater_than("age", "21")
+        .or()
+        .inside("department", vec!["IT", "HR"])
+        .and()
+        .like("email", "%@company.com")
+        .and()
+        .is_not_null("manager_id")
+        .or()
+        .not_like("role", "intern")
+        .and()
+        .nest()
+        .not_inside("location", vec!["Remote", "O'nsite"])
+        .unnest();
+    assert_eq!(
+        condition.build().unwrap(),
+        "name = 'Dayvson Spacca' AND age > '21' OR department IN ('IT', 'HR') AND email LIKE '%@company.com' AND manager_id IS NOT NULL OR role NOT LIKE 'intern' AND (location NOT IN ('Remote', 'O''nsite'))"
+    );
+
+    assert!(matches!(
+        Select::new("t").condition(Where::new()).build(),
+        Err(SqlBuilderError::EmptyCondition)
+    ));
+    assert!(matches!(
+        Select::new("t")
+            .group_by(["a"])
+            .having(Where::new())
+            .build(),
+        Err(SqlBuilderError::EmptyCondition)
+    ));
+    assert!(matches!(
+        Update::new("t")
+            .set(vec![("a", 1)])
+            .condition(Where::new())
+            .build(),
+        Err(SqlBuilderError::EmptyCondition)
+    ));
+    assert!(matches!(
+        Delete::new("t").condition(Where::new()).build(),
+        Err(SqlBuilderError::EmptyCondition)
+    ));
+}
+
+#[test]
+fn test_update_clause() {
+    let update = Update::new("users_tb")
+        .set(vec![("name", "João")])
         .build()
         .unwrap();
+    assert_eq!(update, "UPDATE users_tb SET name = 'João';");
+
+    let update = Update::new("products_tb")
+        .set(vec![("price", "9.99"), ("stock", "100")])
+        .build()
+        .unwrap();
+    assert_eq!(
+        update,
+        "UPDATE products_tb SET price = '9.99', stock = '100';"
+    );
+
+    let mut condition = Where::new();
+    condition.equal_to("age", "21");
+
+    let update = Update::new("users_tb")
+        .set(vec![("name", "João")])
+        .condition(condition)
+        .build()
+        .unwrap();
+    assert_eq!(
+        update,
+        "UPDATE users_tb SET name = 'João' WHERE age = '21';"
+    );
+
+    let update_result = Update::new("users_tb").build();
+    assert!(update_result.is_err());
+    assert_eq!(
+        format!("{}", update_result.unwrap_err()),
+        "The column and the value to be inserted cannot be empty."
+    );
+
+    let update_result = Update::new("").set(vec![("name", "João")]).build();
+    assert!(update_result.is_err());
+    assert_eq!(
+        format!("{}", update_result.unwrap_err()),
+        "Table name cannot be empty."
+    );
+
+    let update_result = Update::new("users_tb").set(vec![("", "João")]).build();
+    assert!(update_result.is_err());
+    assert_eq!(
+        format!("{}", update_result.unwrap_err()),
+        "Column name cannot be empty."
+    );
+
+    let update_result = Update::new("users_tb").set(vec![("name", "")]).build();
+    assert!(update_result.is_err());
+    assert_eq!(
+        format!("{}", update_result.unwrap_err()),
+        "The value cannot be empty."
+    );
+
+    let mut condition = Where::new();
+    condition
+        .equal_to("age", "21")
+        .and()
+        .greater_than("score", "80");
+
+    let update = Update::new("users_tb")
+        .set(vec![("status", "premium")])
+        .condition(condition)
+        .build()
+        .unwrap();
+    assert_eq!(
+        update,
+        "UPDATE users_tb SET status = 'premium' WHERE age = '21' AND score > '80';"
+    );
+
+    let update = Update::new("users_tb")
+        .set(vec![("status", "active")])
+        .build()
+        .unwrap();
+    assert_eq!(update, "UPDATE users_tb SET status = 'active';");
+
+    let update = Update::new("users_tb")
+        .set(vec![("name", "O'Reilly")])
+        .build()
+        .unwrap();
+    assert_eq!(update, "UPDATE users_tb SET name = 'O''Reilly';");
+}
+
+#[test]
+fn test_update_expressions() {
+    let mut update = Update::new("posts")
+        .set(vec![("title", "Hello")])
+        .set_expr("views", Expr::col("views") + 1)
+        .set_expr("updated_at", Expr::raw("CURRENT_TIMESTAMP"))
+        .set_expr("deleted_at", Value::Null);
+    update.condition(Expr::col("id").equal_to(7));
+    assert_eq!(
+        update.build().unwrap(),
+        "UPDATE posts SET title = 'Hello', views = views + 1, \
+         updated_at = CURRENT_TIMESTAMP, deleted_at = NULL WHERE id = 7;"
+    );
+    assert_eq!(Update::from(&update.build().unwrap()).unwrap(), update);
+    assert_eq!(
+        update.build_with_params(Placeholder::Numbered).unwrap().1,
+        vec![
+            Value::from("Hello"),
+            Value::from(1),
+            Value::Null,
+            Value::from(7)
+        ]
+    );
+    assert!(Update::new("posts").set_expr("views", "").build().is_err());
+    assert!(Update::new("posts").set_expr("", 1).build().is_err());
+
+    let mut update = Update::new("inventory")
+        .set_expr("quantity", Expr::col("quantity") - Expr::col("d.amount"));
+    update
+        .from_table("deliveries d")
+        .join_clause(Join::new(
+            "items i",
+            JoinType::Inner,
+            Expr::col("i.id").equal_to(Expr::col("d.item_id")),
+        ))
+        .condition(Expr::col("inventory.item_id").equal_to(Expr::col("i.id")));
+    assert_eq!(
+        update.build().unwrap(),
+        "UPDATE inventory SET quantity = quantity - d.amount FROM deliveries d \
+         INNER JOIN items i ON i.id = d.item_id WHERE inventory.item_id = i.id;"
+    );
+    assert_eq!(Update::from(&update.build().unwrap()).unwrap(), update);
+
+    let mut update = Update::new("posts").set_expr("views", 0);
+    update.join("INNER JOIN users ON users.id = posts.user_id".to_string());
+    assert!(update.build().is_err());
+
+    let mut update = Update::new("jobs").set(vec![("claimed", true)]);
+    update
+        .returning([ResultColumn::from(Expr::col("id"))])
+        .order_by([Expr::col("created_at")])
+        .limit(10)
+        .offset(5);
+    let sql = "UPDATE jobs SET claimed = TRUE RETURNING id ORDER BY created_at LIMIT 10 OFFSET 5;";
+    if cfg!(feature = "update-delete-limit") {
+        assert_eq!(update.build().unwrap(), sql);
+        assert_eq!(Update::from(sql).unwrap(), update);
+        let mut update = Update::new("jobs").set(vec![("claimed", true)]);
+        update.order("created_at");
+        assert!(update.build().is_err());
+    } else {
+        assert!(matches!(
+            update.build(),
+            Err(SqlBuilderError::UpdateDeleteLimitDisabled)
+        ));
+    }
+}
+
+#[test]
+fn test_delete_clause() {
+    let delete = Delete::new("users_tb").build().unwrap();
+    assert_eq!(delete, "DELETE FROM users_tb;");
+
+    let mut condition = Where::new();
+    condition.equal_to("age", "21");
+
+    let delete = Delete::new("users_tb")
+        .condition(condition)
+        .build()
+        .unwrap();
+    assert_eq!(delete, "DELETE FROM users_tb WHERE age = '21';");
+
+    let delete_result = Delete::new("").build();
+    assert!(delete_result.is_err());
+    assert_eq!(
+        format!("{}", delete_result.unwrap_err()),
+        "Table name cannot be empty."
+    );
+
+    let mut condition = Where::new();
+    condition
+        .equal_to("age", "21")
+        .and()
+        .greater_than("score", "80");
+
+    let delete = Delete::new("users_tb")
+        .condition(condition)
+        .build()
+        .unwrap();
+    assert_eq!(
+        delete,
+        "DELETE FROM users_tb WHERE age = '21' AND score > '80';"
+    );
+
+    let delete_result = Delete::new("users_tb").condition(Expr::raw("")).build();
+    assert!(delete_result.is_err());
+    assert_eq!(
+        format!("{}", delete_result.unwrap_err()),
+        "The conditions cannot be empty."
+    );
+
+    let delete = Delete::new("users_tb").build().unwrap();
+
+    assert_eq!(delete, "DELETE FROM users_tb;");
+}
+
+#[test]
+fn test_join_build() {
+    let join = Join::new(
+        "phones p",
+        JoinType::Inner,
+        Expr::col("p.user_id").equal_to(Expr::col("u.user_id")),
+    )
+    .build()
+    .unwrap();
+    assert_eq!(join, "INNER JOIN phones p ON p.user_id = u.user_id");
+
+    let join = Join::new(
+        "addresses a",
+        JoinType::Left,
+        Expr::col("a.user_id").equal_to(Expr::col("u.user_id")),
+    )
+    .build()
+    .unwrap();
     assert_eq!(join, "LEFT JOIN addresses a ON a.user_id = u.user_id");
 
-    let join = Join::new("orders o", JoinType::Right, "o.user_id = u.user_id")
-        .build()
-        .unwrap();
+    let join = Join::new(
+        "orders o",
+        JoinType::Right,
+        Expr::col("o.user_id").equal_to(Expr::col("u.user_id")),
+    )
+    .build()
+    .unwrap();
     assert_eq!(join, "RIGHT JOIN orders o ON o.user_id = u.user_id");
 
-    let join = Join::new("payments p", JoinType::RightOuter, "p.user_id = u.user_id")
-        .build()
-        .unwrap();
+    let join = Join::new(
+        "payments p",
+        JoinType::RightOuter,
+        Expr::col("p.user_id").equal_to(Expr::col("u.user_id")),
+    )
+    .build()
+    .unwrap();
     assert_eq!(join, "RIGHT OUTER JOIN payments p ON p.user_id = u.user_id");
 
-    let join = Join::new("invoices i", JoinType::LeftOuter, "i.user_id = u.user_id")
-        .build()
-        .unwrap();
+    let join = Join::new(
+        "invoices i",
+        JoinType::LeftOuter,
+        Expr::col("i.user_id").equal_to(Expr::col("u.user_id")),
+    )
+    .build()
+    .unwrap();
     assert_eq!(join, "LEFT OUTER JOIN invoices i ON i.user_id = u.user_id");
 
     let join = Join::new(
         "products pr",
         JoinType::Full,
-        "pr.category_id = c.category_id",
+        Expr::col("pr.category_id").equal_to(Expr::col("c.category_id")),
     )
     .build()
     .unwrap();
@@ -620,4 +2369,815 @@ fn test_join_build() {
         join,
         "FULL JOIN products pr ON pr.category_id = c.category_id"
     );
+    let join = Join::new("roles r", JoinType::Cross, Where::new())
+        .build()
+        .unwrap();
+    assert_eq!(join, "CROSS JOIN roles r");
+
+    let join = Join::new(
+        "teams t",
+        JoinType::FullOuter,
+        Expr::col("t.id").equal_to(Expr::col("u.team_id")),
+    )
+    .build()
+    .unwrap();
+    assert_eq!(join, "FULL OUTER JOIN teams t ON t.id = u.team_id");
+
+    let join = Join::new("teams t", JoinType::Inner, Where::new()).build();
+    assert!(join.is_err());
+
+    let mut select = Select::new("users u");
+    select.join_clause(Join::new(
+        "phones p",
+        JoinType::Left,
+        Expr::col("p.user_id")
+            .equal_to(Expr::col("u.id"))
+            .and(Expr::col("p.kind").equal_to("mobile")),
+    ));
+    assert_eq!(
+        select.build_with_params(Placeholder::Numbered).unwrap(),
+        (
+            "SELECT * FROM users u LEFT JOIN phones p ON p.user_id = u.id AND p.kind = ?1;"
+                .to_string(),
+            vec![Value::from("mobile")]
+        )
+    );
+    assert_eq!(Select::from(&select.build().unwrap()).unwrap(), select);
+}
+
+#[test]
+fn test_build_with_params() {
+    let mut condition = Where::new();
+    condition
+        .equal_to("status", "active")
+        .and()
+        .inside("department", vec!["IT", "HR"]);
+    let (sql, params) = condition.build_with_params(Placeholder::Anonymous).unwrap();
+    assert_eq!(sql, "status = ? AND department IN (?, ?)");
+    assert_eq!(
+        params,
+        vec![Value::from("active"), Value::from("IT"), Value::from("HR")]
+    );
+
+    let mut condition = Where::new();
+    condition
+        .greater_than("age", "18")
+        .and()
+        .like("name", "O'Brien%");
+    let (sql, params) = Select::new("users")
+        .columns("name, age")
+        .condition(condition)
+        .limit(10)
+        .build_with_params(Placeholder::Numbered)
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT name, age FROM users WHERE age > ?1 AND name LIKE ?2 LIMIT 10;"
+    );
+    assert_eq!(params, vec![Value::from("18"), Value::from("O'Brien%")]);
+
+    let (sql, params) = Insert::new("users")
+        .values(vec![("name", "João"), ("age", "30")])
+        .build_with_params(Placeholder::Numbered)
+        .unwrap();
+    assert_eq!(sql, "INSERT INTO users (name, age) VALUES (?1, ?2);");
+    assert_eq!(params, vec![Value::from("João"), Value::from("30")]);
+
+    let mut condition = Where::new();
+    condition.equal_to("id", "7");
+    let (sql, params) = Update::new("users")
+        .set(vec![("name", "João"), ("city", "São Paulo")])
+        .condition(condition)
+        .build_with_params(Placeholder::Anonymous)
+        .unwrap();
+    assert_eq!(sql, "UPDATE users SET name = ?, city = ? WHERE id = ?;");
+    assert_eq!(
+        params,
+        vec![
+            Value::from("João"),
+            Value::from("São Paulo"),
+            Value::from("7")
+        ]
+    );
+
+    let mut condition = Where::new();
+    condition.is_null("deleted_at").or().equal_to("id", "7");
+    let (sql, params) = Delete::new("users")
+        .condition(condition)
+        .build_with_params(Placeholder::Numbered)
+        .unwrap();
+    assert_eq!(
+        sql,
+        "DELETE FROM users WHERE deleted_at IS NULL OR id = ?1;"
+    );
+    assert_eq!(params, vec![Value::from("7")]);
+
+    let (sql, params) = Delete::new("users")
+        .build_with_params(Placeholder::Anonymous)
+        .unwrap();
+    assert_eq!(sql, "DELETE FROM users;");
+    assert!(params.is_empty());
+
+    let result = Insert::new("").build_with_params(Placeholder::Anonymous);
+    assert!(result.is_err());
+}
+
+/// Reads one SQLite string literal starting at `i`, returning its content and the next index.
+fn scan_string_literal(sql: &[char], mut i: usize) -> Option<(String, usize)> {
+    if sql.get(i) != Some(&'\'') {
+        return None;
+    }
+    i += 1;
+
+    let mut content = String::new();
+    loop {
+        match sql.get(i)? {
+            '\'' if sql.get(i + 1) == Some(&'\'') => {
+                content.push('\'');
+                i += 2;
+            }
+            '\'' => return Some((content, i + 1)),
+            '\0' => return None,
+            c => {
+                content.push(*c);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Decodes the output of `escape_value`, failing if anything is left outside the literal.
+fn decode_literal(sql: &str) -> Option<String> {
+    let sql = sql.chars().collect::<Vec<_>>();
+    let splice = " || char(0) || ".chars().collect::<Vec<_>>();
+
+    if sql.first() != Some(&'(') {
+        let (content, end) = scan_string_literal(&sql, 0)?;
+        return (end == sql.len()).then_some(content);
+    }
+
+    let mut decoded = String::new();
+    let mut i = 1;
+    loop {
+        let (content, end) = scan_string_literal(&sql, i)?;
+        decoded.push_str(&content);
+        if sql[end..].starts_with(&splice) {
+            decoded.push('\0');
+            i = end + splice.len();
+        } else {
+            return (sql[end..] == [')']).then_some(decoded);
+        }
+    }
+}
+
+fn hostile_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<String>(),
+        "(['\"\\\\;\\x00 a-z]|--|/\\*|\\*/|' OR '1'='1)*",
+    ]
+}
+
+fn column_name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-zA-Z_][a-zA-Z0-9_$ ]{0,8}",
+        Just("order".to_string()),
+        Just("SELECT".to_string()),
+        Just("from".to_string()),
+        Just("first name".to_string()),
+    ]
+}
+
+fn result_column() -> impl Strategy<Value = ResultColumn> {
+    prop_oneof![
+        (column_name(), proptest::option::of(column_name())).prop_map(
+            |(name, alias)| match alias {
+                Some(alias) => ResultColumn::aliased(Expr::col(&name), &alias),
+                None => ResultColumn::from(Expr::col(&name)),
+            }
+        ),
+        (column_name(), column_name()).prop_map(|(table, name)| {
+            ResultColumn::from(Expr::col(&format!("\"{}\".\"{}\"", table, name)))
+        }),
+        column_name().prop_map(|table| ResultColumn::all_from(&table)),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_result_columns_round_trip(columns in proptest::collection::vec(result_column(), 1..5)) {
+        let mut select = Select::new("users");
+        for column in columns {
+            match column {
+                ResultColumn::Expr(expr, Some(alias)) => select.column_as(expr, &alias),
+                ResultColumn::Expr(expr, None) => select.column(expr),
+                column => select.columns([column]),
+            };
+        }
+        let sql = select.build().unwrap();
+        prop_assert_eq!(Select::from(&sql).unwrap(), select);
+    }
+
+    #[test]
+    fn test_escape_value_stays_inside_literal(value in hostile_string()) {
+        let literal = escape_value(&value);
+        prop_assert_eq!(decode_literal(&literal), Some(value));
+    }
+
+    #[test]
+    fn test_builders_use_escape_value(value in hostile_string()) {
+        prop_assume!(!value.is_empty());
+        let literal = escape_value(&value);
+
+        let built = Insert::new("users").values(vec![("name", &value)]);
+        let insert = built.build().unwrap();
+        prop_assert_eq!(&insert, &format!("INSERT INTO users (name) VALUES ({});", literal));
+        prop_assert_eq!(Insert::from(&insert).unwrap(), built);
+
+        let update = Update::new("users").set(vec![("name", &value)]).build().unwrap();
+        prop_assert_eq!(update, format!("UPDATE users SET name = {};", literal));
+
+        let mut condition = Where::new();
+        condition.equal_to("name", &value);
+        prop_assert_eq!(condition.build().unwrap(), format!("name = {}", literal));
+    }
+}
+
+#[test]
+fn test_values() {
+    let insert = Insert::new("users")
+        .values(vec![
+            ("name", Value::from("João")),
+            ("age", Value::from(30)),
+            ("score", Value::from(9.5)),
+            ("active", Value::from(true)),
+            ("avatar", Value::from(vec![0xCA, 0xFE, 0x01])),
+            ("manager_id", Value::from(None::<i64>)),
+            ("team_id", Value::from(Some(3))),
+        ])
+        .build()
+        .unwrap();
+    assert_eq!(
+        insert,
+        "INSERT INTO users (name, age, score, active, avatar, manager_id, team_id) VALUES ('João', 30, 9.5, TRUE, X'CAFE01', NULL, 3);"
+    );
+
+    let update = Update::new("users")
+        .set(vec![("age", 31), ("level", -2)])
+        .build()
+        .unwrap();
+    assert_eq!(update, "UPDATE users SET age = 31, level = -2;");
+
+    let update = Update::new("users")
+        .set(vec![
+            ("manager_id", Value::Null),
+            ("ratio", Value::from(1.0)),
+        ])
+        .build()
+        .unwrap();
+    assert_eq!(update, "UPDATE users SET manager_id = NULL, ratio = 1.0;");
+
+    let mut condition = Where::new();
+    condition
+        .greater_than("age", 21)
+        .and()
+        .equal_to("active", false)
+        .and()
+        .inside("id", vec![1, 2, 3]);
+    assert_eq!(
+        condition.build().unwrap(),
+        "age > 21 AND active = FALSE AND id IN (1, 2, 3)"
+    );
+
+    let (sql, params) = Insert::new("files")
+        .values(vec![
+            ("data", Value::Blob(vec![0, 1])),
+            ("size", Value::from(2)),
+        ])
+        .build_with_params(Placeholder::Anonymous)
+        .unwrap();
+    assert_eq!(sql, "INSERT INTO files (data, size) VALUES (?, ?);");
+    assert_eq!(params, vec![Value::Blob(vec![0, 1]), Value::Integer(2)]);
+
+    let col = Column::new("nickname")
+        .text()
+        .default("O'Neil")
+        .build()
+        .unwrap();
+    assert_eq!(col, "nickname TEXT DEFAULT 'O''Neil'");
+
+    let col = Column::new("deleted_at")
+        .datetime()
+        .default(Value::Null)
+        .build()
+        .unwrap();
+    assert_eq!(col, "deleted_at DATETIME DEFAULT NULL");
+
+    let col = Column::new("created_at")
+        .datetime()
+        .default_expr("CURRENT_TIMESTAMP")
+        .build()
+        .unwrap();
+    assert_eq!(col, "created_at DATETIME DEFAULT CURRENT_TIMESTAMP");
+
+    let update = Update::new("stats")
+        .set(vec![
+            ("max", f64::INFINITY),
+            ("min", f64::NEG_INFINITY),
+            ("avg", f64::NAN),
+        ])
+        .build()
+        .unwrap();
+    assert_eq!(
+        update,
+        "UPDATE stats SET max = 1e999, min = -1e999, avg = NULL;"
+    );
+}
+
+#[test]
+fn test_expressions() {
+    let expr = Expr::col("a")
+        .equal_to(1)
+        .or(Expr::col("b").equal_to(2))
+        .and(Expr::col("c").equal_to(3));
+    assert_eq!(expr.build().unwrap(), "(a = 1 OR b = 2) AND c = 3");
+
+    let expr = Expr::col("a")
+        .equal_to(1)
+        .and(Expr::col("b").equal_to(2))
+        .or(Expr::col("c").equal_to(3));
+    assert_eq!(expr.build().unwrap(), "a = 1 AND b = 2 OR c = 3");
+
+    let expr = Expr::col("a").and(Expr::col("b").and(Expr::col("c")));
+    assert_eq!(expr.build().unwrap(), "a AND (b AND c)");
+
+    let expr = (Expr::col("price") + 1) * Expr::col("qty") - Expr::col("discount") / 2;
+    assert_eq!(expr.build().unwrap(), "(price + 1) * qty - discount / 2");
+
+    let expr = Expr::col("a") - (Expr::col("b") - Expr::col("c"));
+    assert_eq!(expr.build().unwrap(), "a - (b - c)");
+
+    let expr = Expr::col("a") - -2;
+    assert_eq!(expr.build().unwrap(), "a - -2");
+
+    let expr = Expr::col("name").equal_to("Bob'; DROP TABLE x; --");
+    assert_eq!(expr.build().unwrap(), "name = 'Bob''; DROP TABLE x; --'");
+    assert_eq!(
+        Expr::from(String::from("age > 18")).build().unwrap(),
+        "'age > 18'"
+    );
+    assert_eq!(Expr::raw("age > 18").build().unwrap(), "age > 18");
+
+    let expr = -Expr::value(-2);
+    assert_eq!(expr.build().unwrap(), "-(-2)");
+
+    let expr = !Expr::col("a").equal_to(1).or(Expr::col("b").is_null());
+    assert_eq!(expr.build().unwrap(), "NOT (a = 1 OR b IS NULL)");
+
+    let expr = !Expr::col("deleted").equal_to(true);
+    assert_eq!(expr.build().unwrap(), "NOT deleted = TRUE");
+
+    let expr = Expr::col("first_name")
+        .concat(Expr::value(" "))
+        .concat(Expr::col("last_name"))
+        .like(Expr::value("J%"));
+    assert_eq!(
+        expr.build().unwrap(),
+        "first_name || ' ' || last_name LIKE 'J%'"
+    );
+
+    let expr = Expr::col("age")
+        .between(18, 65)
+        .and(Expr::col("id").not_inside(vec![1, 2]));
+    assert_eq!(
+        expr.build().unwrap(),
+        "age BETWEEN 18 AND 65 AND id NOT IN (1, 2)"
+    );
+
+    let expr = Expr::func("lower", vec![Expr::col("email")]).equal_to(Expr::value("a@b.com"));
+    assert_eq!(expr.build().unwrap(), "lower(email) = 'a@b.com'");
+
+    let expr = Expr::from(
+        Case::on(Expr::col("status"))
+            .when(Expr::value("a"), Expr::value("Active"))
+            .when(Expr::value("p"), Expr::value("Pending"))
+            .otherwise(Value::Null),
+    );
+    assert_eq!(
+        expr.build().unwrap(),
+        "CASE status WHEN 'a' THEN 'Active' WHEN 'p' THEN 'Pending' ELSE NULL END"
+    );
+    assert!(Expr::from(Case::new()).build().is_err());
+
+    let mut max_age = Select::new("users");
+    max_age.columns("max(age)");
+    let expr = Expr::col("age").equal_to(max_age);
+    assert_eq!(expr.build().unwrap(), "age = (SELECT max(age) FROM users)");
+
+    let select = Select::new("users")
+        .condition(
+            Expr::col("age")
+                .greater_than_equal(18)
+                .and(Expr::col("name").like(Expr::value("D%"))),
+        )
+        .build()
+        .unwrap();
+    assert_eq!(
+        select,
+        "SELECT * FROM users WHERE age >= 18 AND name LIKE 'D%';"
+    );
+
+    let update = Update::new("users")
+        .set(vec![("active", false)])
+        .condition(Expr::col("last_login").is_null())
+        .build()
+        .unwrap();
+    assert_eq!(
+        update,
+        "UPDATE users SET active = FALSE WHERE last_login IS NULL;"
+    );
+
+    let (sql, params) = Delete::new("users")
+        .condition(Expr::col("id").inside(vec![Expr::value(1), Expr::value(2)]))
+        .build_with_params(Placeholder::Numbered)
+        .unwrap();
+    assert_eq!(sql, "DELETE FROM users WHERE id IN (?1, ?2);");
+    assert_eq!(params, vec![Value::Integer(1), Value::Integer(2)]);
+}
+
+#[test]
+fn test_where_is_well_formed() {
+    let mut condition = Where::new();
+    condition.equal_to("a", 1).and();
+    assert_eq!(condition.build().unwrap(), "a = 1");
+
+    let mut condition = Where::new();
+    condition.equal_to("a", 1).or().or().equal_to("b", 2);
+    assert_eq!(condition.build().unwrap(), "a = 1 OR b = 2");
+
+    let mut condition = Where::new();
+    condition.unnest().equal_to("a", 1).unnest();
+    assert_eq!(condition.build().unwrap(), "a = 1");
+
+    let mut condition = Where::new();
+    condition
+        .equal_to("a", 1)
+        .and()
+        .nest()
+        .equal_to("b", 2)
+        .or()
+        .equal_to("c", 3);
+    assert_eq!(condition.build().unwrap(), "a = 1 AND (b = 2 OR c = 3)");
+
+    let mut condition = Where::new();
+    condition.nest().unnest().and().equal_to("a", 1);
+    assert_eq!(condition.build().unwrap(), "a = 1");
+
+    let mut condition = Where::new();
+    condition
+        .expr(Expr::col("a").equal_to(1).or(Expr::col("b").equal_to(2)))
+        .and()
+        .is_not_null("c");
+    assert_eq!(
+        condition.build().unwrap(),
+        "(a = 1 OR b = 2) AND c IS NOT NULL"
+    );
+    assert_eq!(
+        condition.to_expr(),
+        Some(
+            Expr::col("a")
+                .equal_to(1)
+                .or(Expr::col("b").equal_to(2))
+                .and(Expr::col("c").is_not_null())
+        )
+    );
+
+    assert!(Where::new().is_empty());
+    assert_eq!(Where::new().build().unwrap(), "");
+
+    let mut condition = Where::new();
+    condition.expr(Expr::coalesce(vec![Expr::col("a")]));
+    assert!(matches!(
+        condition.build(),
+        Err(SqlBuilderError::InvalidArgumentCount { .. })
+    ));
+
+    let mut condition = Where::new();
+    condition.equal_to("", 1);
+    assert!(matches!(
+        condition.build_with_params(Placeholder::Anonymous),
+        Err(SqlBuilderError::EmptyColumnName)
+    ));
+
+    let delete = Delete::new("users").condition(Where::new()).build();
+    assert!(delete.is_err());
+}
+
+#[test]
+fn test_select_from() {
+    let queries = [
+        "SELECT * FROM users;",
+        "SELECT name, age FROM users WHERE age > 18;",
+        "SELECT DISTINCT city FROM users;",
+        "SELECT u.name AS user_name, count(*) AS total FROM users u INNER JOIN phones p ON p.user_id = u.user_id LEFT JOIN addresses a ON a.user_id = u.user_id GROUP BY u.name ORDER BY total DESC, u.name LIMIT 10 OFFSET 5;",
+        "SELECT * FROM users WHERE (status = 'active' OR status = 'pending') AND created_at > '2024-01-01';",
+        "SELECT \"first name\", \"order\" FROM \"my table\" WHERE \"first name\" = 'Mary Jane O''Neil';",
+        "SELECT * FROM users WHERE age BETWEEN 18 AND 65 AND city NOT IN ('Remote', 'São Paulo') AND email IS NOT NULL;",
+        "SELECT count(DISTINCT city), CAST(price AS DECIMAL(10, 2)) FROM products WHERE data = X'CAFE' AND flag = TRUE AND ratio < -1.5;",
+        "SELECT CASE WHEN age < 18 THEN 'minor' ELSE 'adult' END AS bracket FROM users;",
+        "SELECT * FROM users WHERE age = (SELECT max(age) FROM users);",
+        "SELECT u.* FROM main.users u CROSS JOIN roles r;",
+        "SELECT * FROM users WHERE NOT (a = 1 OR b LIKE 'x%') AND c IS NULL;",
+    ];
+
+    for query in queries {
+        let select = Select::from(query).unwrap();
+        assert_eq!(select.build().unwrap(), query);
+    }
+
+    let select = Select::from(
+        "select name from users as u join phones p on p.user_id = u.id where age >= 21 limit 5, 10",
+    )
+    .unwrap();
+    assert_eq!(
+        select.build().unwrap(),
+        "SELECT name FROM users AS u INNER JOIN phones p ON p.user_id = u.id WHERE age >= 21 LIMIT 10 OFFSET 5;"
+    );
+
+    for sql in [
+        "SELECT * FROM `t`;",
+        "SELECT * FROM \"my table\" AS \"m\" INNER JOIN [u] ON [u].id = \"m\".id;",
+        "SELECT * FROM main.\"users\" u;",
+    ] {
+        let select = Select::from(sql).unwrap();
+        assert_eq!(Select::from(&select.build().unwrap()).unwrap(), select);
+    }
+    assert_eq!(Select::from("SELECT * FROM `t`").unwrap(), Select::new("t"));
+    assert_eq!(
+        Select::from("SELECT * FROM \"my table\" AS m").unwrap(),
+        Select::new("\"my table\" AS \"m\"")
+    );
+
+    for (sql, position) in [
+        ("SELECT * FROM t, u", 15),
+        ("SELECT * FROM t JOIN u ON u.id = t.id, v", 37),
+        ("SELECT * FROM t NATURAL JOIN u", 16),
+        ("SELECT * FROM t JOIN u USING (id)", 23),
+        ("SELECT * FROM t WHERE name COLLATE NOCASE = 'a'", 27),
+        ("SELECT * FROM t WHERE name LIKE 'a!%' ESCAPE '!'", 38),
+        ("UPDATE t SET a = 1 FROM u, v", 25),
+    ] {
+        let error = match sql.starts_with("UPDATE") {
+            true => Update::from(sql).unwrap_err(),
+            false => Select::from(sql).unwrap_err(),
+        };
+        match error {
+            SqlBuilderError::Unsupported { position: p, .. } => assert_eq!(p, position, "{}", sql),
+            e => panic!("unexpected error for {}: {}", sql, e),
+        }
+    }
+    assert_eq!(
+        Select::from("SELECT * FROM t ORDER BY name COLLATE NOCASE")
+            .unwrap()
+            .build()
+            .unwrap(),
+        "SELECT * FROM t ORDER BY name COLLATE NOCASE;"
+    );
+
+    let select =
+        Select::from("SELECT * FROM users -- trailing comment\n WHERE /* note */ age <> 3")
+            .unwrap()
+            .build()
+            .unwrap();
+    assert_eq!(select, "SELECT * FROM users WHERE age != 3;");
+
+    let select = Select::from("SELECT * FROM t WHERE a = 0xFF AND b = 0xFFFFFFFFFFFFFFFF")
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(select, "SELECT * FROM t WHERE a = 255 AND b = -1;");
+    assert!(matches!(
+        Select::from("SELECT * FROM t WHERE a = -0x8000000000000000"),
+        Err(SqlBuilderError::UnexpectedToken { .. })
+    ));
+    assert!(Select::from("SELECT * FROM t WHERE a = 0x10000000000000000").is_err());
+
+    let mut condition = Where::new();
+    condition
+        .greater_than("age", 18)
+        .and()
+        .equal_to("city", "São Paulo");
+    let mut expected = Select::new("users");
+    expected
+        .columns([Expr::col("name"), Expr::col("age")])
+        .condition(condition)
+        .order_by([Expr::col("name")])
+        .limit(10);
+    let parsed = Select::from(
+        "SELECT name, age FROM users WHERE age > 18 AND city = 'São Paulo' ORDER BY name LIMIT 10",
+    )
+    .unwrap();
+    assert_eq!(parsed, expected);
+
+    let (sql, params) = Select::from("SELECT * FROM users WHERE name = 'João' AND age > 30")
+        .unwrap()
+        .build_with_params(Placeholder::Anonymous)
+        .unwrap();
+    assert_eq!(sql, "SELECT * FROM users WHERE name = ? AND age > ?;");
+    assert_eq!(params, vec![Value::from("João"), Value::from(30)]);
+
+    match Select::from("SELECT name FROM users WHERE").unwrap_err() {
+        SqlBuilderError::UnexpectedToken {
+            position,
+            expected,
+            found,
+        } => {
+            assert_eq!(position, 28);
+            assert_eq!(expected, "expression");
+            assert_eq!(found, "end of input");
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+
+    let error = Select::from("SELECT name, FROM users").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Expected expression at position 13, found 'FROM'."
+    );
+
+    let error = Select::from("SELECT * FROM users WHERE name = 'open").unwrap_err();
+    assert!(matches!(
+        error,
+        SqlBuilderError::UnexpectedToken { position: 33, .. }
+    ));
+
+    assert!(Select::from("SELECT * FROM users LIMIT -1").is_err());
+    assert!(Select::from("SELECT * FROM users u JOIN phones p").is_err());
+    assert!(Select::from("SELECT * FROM users WHERE a = 1 b").is_err());
+    assert!(Select::from("UPDATE users SET a = 1").is_err());
+}
+
+#[test]
+fn test_round_trip() {
+    let inserts = [
+        Insert::new("users").values(vec![("name", "João"), ("city", "São Paulo")]),
+        Insert::new("users").values(vec![
+            ("name", Value::from("M' Carlos")),
+            ("age", Value::from(30)),
+            ("score", Value::from(-9.25)),
+            ("active", Value::from(false)),
+            ("avatar", Value::from(vec![0xCA, 0xFE])),
+            ("manager_id", Value::Null),
+        ]),
+    ];
+    for insert in inserts {
+        assert_eq!(Insert::from(&insert.build().unwrap()).unwrap(), insert);
+    }
+
+    let mut condition = Where::new();
+    condition
+        .nest()
+        .equal_to("status", "active")
+        .or()
+        .less_than("age", 18)
+        .unnest()
+        .and()
+        .inside("city", vec!["Recife", "Natal"]);
+    let mut update = Update::new("users").set(vec![
+        ("name", Value::from("O'Reilly")),
+        ("age", Value::from(31)),
+    ]);
+    update.condition(Expr::from(condition.clone()));
+    let mut updates = vec![update, Update::new("users").set(vec![("active", true)])];
+    updates[1].condition(
+        Expr::col("last_login")
+            .is_null()
+            .or(Expr::col("age").not_between(18, 65)),
+    );
+    for update in updates {
+        assert_eq!(Update::from(&update.build().unwrap()).unwrap(), update);
+    }
+
+    let mut delete = Delete::new("users");
+    delete.condition(
+        Expr::col("id")
+            .equal_to(7)
+            .and(Expr::col("name").like(Expr::value("%a%"))),
+    );
+    for delete in [Delete::new("users"), delete] {
+        assert_eq!(Delete::from(&delete.build().unwrap()).unwrap(), delete);
+    }
+
+    let create_tables = [
+        CreateTable::new(
+            "users",
+            vec![
+                Column::new("id").integer().primary_key().auto_increment(),
+                Column::new("name").text().not_null().unique(),
+                Column::new("score").real().default(0.5),
+                Column::new("nickname").text().default("O'Neil"),
+                Column::new("balance").numeric().default(-10),
+                Column::new("avatar").blob(),
+                Column::new("created_at")
+                    .datetime()
+                    .default_expr("CURRENT_TIMESTAMP"),
+                Column::new("expires_at")
+                    .date()
+                    .default_expr("(date('now', '+1 day'))"),
+            ],
+        ),
+        CreateTable::new("events", vec![Column::new("at").time().not_null()]).if_not_exists(),
+        CreateTable::new(
+            "flags",
+            vec![Column::new("enabled").boolean().default(true)],
+        ),
+        CreateTable::new(
+            "stats",
+            vec![
+                Column::new("ratio")
+                    .custom_type("DOUBLE PRECISION")
+                    .not_null(),
+                Column::new("label").custom_type("VARYING CHARACTER(20)"),
+            ],
+        ),
+        CreateTable::new(
+            "pairs",
+            vec![Column::new("a").untyped(), Column::new("b").untyped()],
+        ),
+    ];
+    for create_table in create_tables {
+        assert_eq!(
+            CreateTable::from(&create_table.build().unwrap()).unwrap(),
+            create_table
+        );
+    }
+
+    let mut select = Select::new("users u");
+    select
+        .distinct()
+        .columns([Expr::col("u.name")])
+        .column_as(Expr::count_all(), "total")
+        .join_clause(Join::new(
+            "phones p",
+            JoinType::Inner,
+            Expr::col("p.user_id").equal_to(Expr::col("u.id")),
+        ))
+        .condition(condition)
+        .group_by([Expr::col("u.name"), Expr::lower(Expr::col("u.city"))])
+        .order_by([Expr::col("total").desc()])
+        .limit(5)
+        .offset(10);
+    assert_eq!(Select::from(&select.build().unwrap()).unwrap(), select);
+
+    assert_eq!(
+        Expr::col("a")
+            .or(Expr::col("b"))
+            .nested()
+            .and(Expr::col("c")),
+        Expr::col("a").or(Expr::col("b")).and(Expr::col("c"))
+    );
+    let mut delete = Delete::new("users");
+    delete.condition(
+        Expr::col("a")
+            .equal_to(1)
+            .nested()
+            .and(Expr::col("b").is_null().nested()),
+    );
+    assert_eq!(
+        delete.build().unwrap(),
+        "DELETE FROM users WHERE (a = 1) AND (b IS NULL);"
+    );
+    assert_eq!(Delete::from(&delete.build().unwrap()).unwrap(), delete);
+
+    let col = Column::new("salary").real().build().unwrap();
+    assert_eq!(col, "salary REAL");
+
+    assert!(Insert::from("INSERT INTO users (a, b) VALUES (1)").is_err());
+    assert!(Insert::from("INSERT INTO users (a) VALUES (a + 1)").is_err());
+    assert!(Update::from("UPDATE users SET a = 1 WHERE").is_err());
+    assert!(Delete::from("DELETE users").is_err());
+    for sql in [
+        "CREATE TABLE t (a DOUBLE PRECISION);",
+        "CREATE TABLE t (a, b);",
+        "CREATE TABLE t (a VARCHAR(10), b);",
+        "CREATE TABLE t (a UNSIGNED BIG INT NOT NULL, b PRIMARY KEY);",
+    ] {
+        assert_eq!(CreateTable::from(sql).unwrap().build().unwrap(), sql);
+    }
+    assert_eq!(
+        CreateTable::from("CREATE TABLE t (a, b)").unwrap(),
+        CreateTable::new(
+            "t",
+            vec![Column::new("a").untyped(), Column::new("b").untyped()]
+        )
+    );
+
+    assert_eq!(
+        Update::from("UPDATE users SET name = upper(name)").unwrap(),
+        Update::new("users").set_expr("name", Expr::func("upper", vec![Expr::col("name")]))
+    );
+    let error = Insert::from("INSERT INTO users (name) VALUES (upper('a'))").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Expressions in VALUES at position 33 are not supported."
+    );
 }